@@ -7,6 +7,7 @@ pub mod error;
 pub mod log;
 pub mod render;
 pub mod mouse;
+pub mod sdf;
 
 pub type Color = SDL_FColor;
 
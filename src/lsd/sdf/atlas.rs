@@ -0,0 +1,90 @@
+use etagere::{size2, AllocId, BucketedAtlasAllocator, Rectangle, Size};
+
+/// Where a rectangle was allocated by an [`AtlasBuilder`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AtlasAllocation {
+    /// The index of the page in [`AtlasBuilder::pages`].
+    pub page: usize,
+    pub id: AllocId,
+    /// The allocated rectangle, which can be larger than requested, with the requested size at its top-left corner.
+    pub rectangle: Rectangle
+}
+
+/// The largest width and height of a page, which is a limit of [`etagere`].
+pub const MAX_PAGE_SIZE: u32 = u16::MAX as u32 - 1;
+
+/// Packs rectangles into pages, growing the last page up to a maximum size
+/// before starting a new one.
+pub struct AtlasBuilder {
+    pages: Vec<BucketedAtlasAllocator>,
+    initial_size: Size,
+    max_size: Size
+}
+
+impl AtlasBuilder {
+    /// Creates a builder whose pages start at `initial_size` and grow up to `max_size`,
+    /// usually the maximum texture size of the GPU. Both are clamped to [`MAX_PAGE_SIZE`].
+    pub fn new(initial_size: (u32, u32), max_size: (u32, u32)) -> Self {
+        let clamp = |(width, height): (u32, u32)| size2(width.clamp(1, MAX_PAGE_SIZE) as i32, height.clamp(1, MAX_PAGE_SIZE) as i32);
+        let max_size = clamp(max_size);
+        let initial_size = clamp(initial_size).min(max_size);
+        AtlasBuilder { pages: vec![], initial_size, max_size }
+    }
+
+    /// Allocates a rectangle of the given size, in the first page where it fits.
+    /// Returns `None` if it's empty, or larger than the maximum size of a page.
+    pub fn allocate(&mut self, width: u32, height: u32) -> Option<AtlasAllocation> {
+        if width == 0 || height == 0 || width > self.max_size.width as u32 || height > self.max_size.height as u32 {
+            return None;
+        }
+        let size = size2(width as i32, height as i32);
+
+        for (page, allocator) in self.pages.iter_mut().enumerate() {
+            if let Some(allocation) = allocator.allocate(size) {
+                return Some(AtlasAllocation { page, id: allocation.id, rectangle: allocation.rectangle });
+            }
+        }
+
+        // only the last page can still grow, the previous ones are already at the maximum size
+        if self.pages.last().is_none_or(|allocator| allocator.size() == self.max_size) {
+            self.pages.push(BucketedAtlasAllocator::new(self.initial_size));
+        }
+        let mut page = self.pages.len() - 1;
+
+        loop {
+            let allocator = &mut self.pages[page];
+            if let Some(allocation) = allocator.allocate(size) {
+                return Some(AtlasAllocation { page, id: allocation.id, rectangle: allocation.rectangle });
+            }
+
+            // the shortest side is doubled, so pages stay roughly square
+            let current = allocator.size();
+            let grow_width = current.width < self.max_size.width
+                && (current.width <= current.height || current.height == self.max_size.height);
+            let grown = if grow_width {
+                size2((current.width*2).min(self.max_size.width), current.height)
+            } else {
+                size2(current.width, (current.height*2).min(self.max_size.height))
+            };
+
+            if grown == current {
+                // the page is full, the rectangle goes to a new one
+                self.pages.push(BucketedAtlasAllocator::new(self.initial_size));
+                page = self.pages.len() - 1;
+            } else {
+                allocator.grow(grown);
+            }
+        }
+    }
+
+    /// Returns the allocators of every page.
+    pub fn pages(&self) -> &[BucketedAtlasAllocator] {
+        &self.pages
+    }
+
+    /// Returns the size of a page, which is the size its image should have.
+    pub fn page_size(&self, page: usize) -> (u32, u32) {
+        let size = self.pages[page].size();
+        (size.width as u32, size.height as u32)
+    }
+}
@@ -0,0 +1,239 @@
+use super::consts::PI;
+
+use super::{shape::Shape, validation::{connect, meets, ShapeError}, vec2, Contour, Float, OutlineBuilder, Segment, Vec2};
+
+/// Builds a [`Shape`] contour by contour, like a pen.
+///
+/// Primitives ([`ShapeBuilder::rect`], [`ShapeBuilder::circle`], ...) are each drawn as their own
+/// clockwise contour, which is the orientation of filled shapes.
+/// Draw nested contours counter-clockwise (or use [`Shape::orient_contours`]) to make holes.
+///
+/// Misuses of the pen, like drawing before `move_to`, are worked around and recorded in [`ShapeBuilder::errors`].
+///
+/// Also implements [`OutlineBuilder`], to build shapes from the outlines of glyphs.
+#[derive(Default)]
+pub struct ShapeBuilder {
+    contours: Vec<Contour>,
+    current: Option<Contour>,
+    start: Vec2,
+    cur_pos: Vec2,
+    errors: Vec<ShapeError>
+}
+
+/// Approximates an arc of the unit circle with cubic bezier curves of at most a quarter turn each.
+/// Returns the two control points and the end point of each curve.
+pub fn arc_cubics(start_angle: Float, sweep_angle: Float) -> impl Iterator<Item = (Vec2, Vec2, Vec2)> {
+    let count = (sweep_angle.abs()/(PI/2.0) - 1e-4).ceil().max(1.0) as usize;
+    let step = sweep_angle/count as Float;
+    let k = 4.0/3.0*(step/4.0).tan();
+
+    (0..count).map(move |i| {
+        let a0 = start_angle + step*i as Float;
+        let a1 = a0 + step;
+        let (p0, p1) = (vec2(a0.cos(), a0.sin()), vec2(a1.cos(), a1.sin()));
+        (p0 + k*p0.orthogonal(true), p1 - k*p1.orthogonal(true), p1)
+    })
+}
+
+impl ShapeBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts a new contour at the given point.
+    /// A contour still being drawn is closed, and recorded as [`ShapeError::UnclosedContour`] if it has edges.
+    pub fn move_to(&mut self, p: Vec2) -> &mut Self {
+        self.end();
+        self.current = Some(Contour { edges: vec![] });
+        self.start = p;
+        self.cur_pos = p;
+        self
+    }
+
+    fn push(&mut self, segment: Segment, next: Vec2) -> &mut Self {
+        if self.current.is_none() {
+            self.errors.push(ShapeError::MissingMoveTo);
+            self.move_to(self.cur_pos);
+        }
+        self.current.as_mut().unwrap().edges.push(segment.white_edge());
+        self.cur_pos = next;
+        self
+    }
+
+    pub fn line_to(&mut self, p: Vec2) -> &mut Self {
+        self.push(Segment::Line(self.cur_pos, p), p)
+    }
+
+    pub fn quad_to(&mut self, control: Vec2, p: Vec2) -> &mut Self {
+        self.push(Segment::Quad(self.cur_pos, control, p), p)
+    }
+
+    pub fn cubic_to(&mut self, control1: Vec2, control2: Vec2, p: Vec2) -> &mut Self {
+        self.push(Segment::Cubic(self.cur_pos, control1, control2, p), p)
+    }
+
+    /// Draws an arc of ellipse, with angles in radians, going counter-clockwise for a positive `sweep_angle`.
+    ///
+    /// If a contour is being drawn, a line is added to the start of the arc if the pen isn't already there,
+    /// up to rounding errors, otherwise a new contour is started there.
+    pub fn arc(&mut self, center: Vec2, radii: Vec2, start_angle: Float, sweep_angle: Float) -> &mut Self {
+        let map = |p: Vec2| vec2(center.x + p.x*radii.x, center.y + p.y*radii.y);
+
+        let start = map(vec2(start_angle.cos(), start_angle.sin()));
+        if self.current.is_none() {
+            self.move_to(start);
+        } else if !meets(self.cur_pos, start) {
+            self.line_to(start);
+        }
+
+        for (c1, c2, end) in arc_cubics(start_angle, sweep_angle) {
+            self.cubic_to(map(c1), map(c2), map(end));
+        }
+        self
+    }
+
+    /// Closes the current contour, adding a line back to its start if needed.
+    pub fn close(&mut self) -> &mut Self {
+        if !self.finish_contour() {
+            self.errors.push(ShapeError::RedundantClose);
+        }
+        self
+    }
+
+    /// Closes the current contour if it was left open, which is recorded as an error.
+    pub fn end(&mut self) -> &mut Self {
+        let has_edges = self.current.as_ref().is_some_and(|c| !c.edges.is_empty());
+        if self.finish_contour() && has_edges {
+            self.errors.push(ShapeError::UnclosedContour { contour: self.contours.len() - 1 });
+        }
+        self
+    }
+
+    /// Closes the current contour if there is one, and returns whether there was.
+    /// Contours without edges are dropped, so that errors give the index of contours in the shape.
+    fn finish_contour(&mut self) -> bool {
+        let Some(mut current) = self.current.take() else { return false };
+
+        if let Some(last) = current.edges.last_mut() {
+            if let Some(line) = connect(&mut last.segment, self.start) {
+                current.edges.push(line.white_edge());
+            }
+        }
+        self.cur_pos = self.start;
+        if !current.edges.is_empty() {
+            self.contours.push(current);
+        }
+        true
+    }
+
+    /// Adds a rectangle with its bottom-left corner at `min`.
+    pub fn rect(&mut self, min: Vec2, size: Vec2) -> &mut Self {
+        let max = min + size;
+        self.move_to(min)
+            .line_to(vec2(min.x, max.y))
+            .line_to(max)
+            .line_to(vec2(max.x, min.y))
+            .close()
+    }
+
+    /// Adds a rectangle with its bottom-left corner at `min`, and corners rounded with the given radius.
+    /// The radius is clamped to half of the smallest side.
+    pub fn rounded_rect(&mut self, min: Vec2, size: Vec2, radius: Float) -> &mut Self {
+        let r = radius.min(size.x/2.0).min(size.y/2.0);
+        if r <= 0.0 {
+            return self.rect(min, size);
+        }
+
+        let max = min + size;
+        self.move_to(vec2(min.x, min.y + r))
+            .arc(vec2(min.x + r, max.y - r), vec2(r, r), PI, -PI/2.0)
+            .arc(vec2(max.x - r, max.y - r), vec2(r, r), PI/2.0, -PI/2.0)
+            .arc(vec2(max.x - r, min.y + r), vec2(r, r), 0.0, -PI/2.0)
+            .arc(vec2(min.x + r, min.y + r), vec2(r, r), -PI/2.0, -PI/2.0)
+            .close()
+    }
+
+    pub fn circle(&mut self, center: Vec2, radius: Float) -> &mut Self {
+        self.ellipse(center, vec2(radius, radius))
+    }
+
+    pub fn ellipse(&mut self, center: Vec2, radii: Vec2) -> &mut Self {
+        self.move_to(center + vec2(radii.x, 0.0))
+            .arc(center, radii, 0.0, -2.0*PI)
+            .close()
+    }
+
+    /// Adds a closed polygon going through the given points.
+    /// They need to be in clockwise order for the polygon to be filled.
+    pub fn polygon(&mut self, points: &[Vec2]) -> &mut Self {
+        let Some((&first, rest)) = points.split_first() else { return self };
+
+        self.move_to(first);
+        for &p in rest {
+            self.line_to(p);
+        }
+        self.close()
+    }
+
+    /// Returns the misuses recorded so far.
+    pub fn errors(&self) -> &[ShapeError] {
+        &self.errors
+    }
+
+    /// Closes the current contour, and returns every contour drawn.
+    pub fn into_contours(mut self) -> Vec<Contour> {
+        self.end();
+        self.contours
+    }
+
+    /// Creates the shape, with bounds computed from its geometry, repairing any problem.
+    /// The shape is normalized, see [`Shape::normalize`].
+    pub fn build(self) -> Shape {
+        let mut shape = Shape::from_contours(self.into_contours());
+        shape.repair();
+        shape.normalize();
+        shape
+    }
+
+    /// Creates the shape, with bounds computed from its geometry,
+    /// failing if the pen was misused or if the shape doesn't pass [`Shape::validate`].
+    /// The shape is normalized, see [`Shape::normalize`].
+    pub fn try_build(mut self) -> Result<Shape, ShapeError> {
+        self.end();
+        if let Some(&error) = self.errors.first() {
+            return Err(error);
+        }
+
+        let mut shape = Shape::from_contours(self.contours);
+        shape.validate()?;
+        shape.normalize();
+        Ok(shape)
+    }
+}
+
+/// Converts a point given by ttf-parser.
+fn point(x: f32, y: f32) -> Vec2 {
+    vec2(x as Float, y as Float)
+}
+
+impl OutlineBuilder for ShapeBuilder {
+    fn move_to(&mut self, x: f32, y: f32) {
+        ShapeBuilder::move_to(self, point(x, y));
+    }
+
+    fn close(&mut self) {
+        ShapeBuilder::close(self);
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        ShapeBuilder::line_to(self, point(x, y));
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        ShapeBuilder::quad_to(self, point(x1, y1), point(x, y));
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        ShapeBuilder::cubic_to(self, point(x1, y1), point(x2, y2), point(x, y));
+    }
+}
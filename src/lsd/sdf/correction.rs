@@ -0,0 +1,140 @@
+use image::{GenericImage, Rgba};
+
+/// Which texels [`correct_errors`] is allowed to modify.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorCorrectionMode {
+    /// Leaves the image untouched.
+    Off,
+    /// Corrects artifacts, except on texels that define an edge agreeing with the true distance.
+    #[default]
+    EdgePriority,
+    /// Corrects every detected artifact, even if it slightly moves the edges of the shape.
+    Full
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ErrorCorrectionConfig {
+    pub mode: ErrorCorrectionMode,
+    /// How far the interpolated median has to stray from the interpolated true distance to be considered an artifact,
+    /// relative to the change of true distance between the two interpolated texels.
+    /// Default value is `1.11`.
+    pub min_deviation_ratio: f32
+}
+
+impl Default for ErrorCorrectionConfig {
+    fn default() -> Self {
+        Self {
+            mode: ErrorCorrectionMode::EdgePriority,
+            min_deviation_ratio: 1.11
+        }
+    }
+}
+
+fn median(p: [f32; 4]) -> f32 {
+    let [r, g, b, _] = p;
+    r.min(g).max(r.max(g).min(b))
+}
+
+fn is_inside(value: f32) -> bool {
+    value > 0.5
+}
+
+fn lerp_texel(a: [f32; 4], b: [f32; 4], t: f32) -> [f32; 4] {
+    [0, 1, 2, 3].map(|i| a[i] + (b[i] - a[i])*t)
+}
+
+/// Looks for an artifact when bilinearly interpolating between the texels `a` and `b`:
+/// a point where the median of the color channels lands on the other side of the edge than the true distance.
+///
+/// The median is piecewise linear along the interpolation, so it only needs to be checked
+/// at both texels and where two channels cross each other.
+/// Returns the interpolation parameter of the artifact, if any.
+fn find_interpolation_artifact(a: [f32; 4], b: [f32; 4], min_deviation_ratio: f32) -> Option<f32> {
+    let tolerance = min_deviation_ratio*(b[3] - a[3]).abs();
+
+    let mut candidates = [0.0, 1.0, -1.0, -1.0, -1.0];
+    for (n, (i, j)) in [(0, 1), (1, 2), (0, 2)].into_iter().enumerate() {
+        let da = a[i] - a[j];
+        let db = b[i] - b[j];
+        if da*db < 0.0 {
+            candidates[2 + n] = da / (da - db);
+        }
+    }
+
+    candidates.into_iter()
+        .filter(|t| (0.0..=1.0).contains(t))
+        .find(|&t| {
+            let p = lerp_texel(a, b, t);
+            let m = median(p);
+            is_inside(m) != is_inside(p[3]) && (m - p[3]).abs() > tolerance
+        })
+}
+
+/// Detects and removes the artifacts of an MTSDF bitmap, as generated by [`super::ColouredShape::generate_mtsdf`].
+///
+/// Artifacts are texels whose interpolated median would be on the wrong side of the edge,
+/// which is detected using the true distance stored in the alpha channel.
+/// The color channels of those texels are equalized, turning them into plain SDF texels.
+///
+/// To correct a single glyph of an atlas, pass in a sub image, for example using [`image::imageops::crop`].
+pub fn correct_errors<I: GenericImage<Pixel = Rgba<f32>>>(image: &mut I, config: &ErrorCorrectionConfig) {
+    if config.mode == ErrorCorrectionMode::Off { return }
+
+    let (width, height) = image.dimensions();
+    let texel = |x: u32, y: u32| image.get_pixel(x, y).0;
+    let index = |x: u32, y: u32| (y*width + x) as usize;
+
+    let mut protected = vec![false; (width*height) as usize];
+    let mut artifacts = vec![false; (width*height) as usize];
+
+    if config.mode == ErrorCorrectionMode::EdgePriority {
+        // protect texels on both sides of an edge when the color channels agree with the true distance
+        for y in 0..height {
+            for x in 0..width {
+                let a = texel(x, y);
+                for (nx, ny) in [(x + 1, y), (x, y + 1)] {
+                    if nx >= width || ny >= height { continue }
+
+                    let b = texel(nx, ny);
+                    if is_inside(a[3]) != is_inside(b[3]) && is_inside(median(a)) != is_inside(median(b)) {
+                        protected[index(x, y)] = true;
+                        protected[index(nx, ny)] = true;
+                    }
+                }
+            }
+        }
+    }
+
+    for y in 0..height {
+        for x in 0..width {
+            let a = texel(x, y);
+            for (dx, dy) in [(1, 0), (0, 1), (1, 1), (-1, 1)] {
+                let (nx, ny) = (x as i64 + dx, y as i64 + dy);
+                if nx < 0 || nx >= width as i64 || ny >= height as i64 { continue }
+                let (nx, ny) = (nx as u32, ny as u32);
+
+                let b = texel(nx, ny);
+                if let Some(t) = find_interpolation_artifact(a, b, config.min_deviation_ratio) {
+                    // blame the texel closest to the artifact
+                    if t <= 0.5 {
+                        artifacts[index(x, y)] = true;
+                    } else {
+                        artifacts[index(nx, ny)] = true;
+                    }
+                }
+            }
+        }
+    }
+
+    for y in 0..height {
+        for x in 0..width {
+            let i = index(x, y);
+            if !artifacts[i] || protected[i] { continue }
+
+            let p = image.get_pixel(x, y).0;
+            let m = median(p);
+            let v = if is_inside(m) == is_inside(p[3]) { m } else { p[3] };
+            image.put_pixel(x, y, Rgba([v, v, v, p[3]]));
+        }
+    }
+}
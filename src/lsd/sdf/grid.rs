@@ -0,0 +1,227 @@
+use std::ops::Range;
+
+use super::{vec2, Bounds, Color, Contour, Float, Vec2};
+
+/// The order in which the edges of a contour are given to the edge selectors.
+pub fn visit_order(len: usize) -> impl Iterator<Item = usize> {
+    (len.saturating_sub(1)..len).chain(0..len.saturating_sub(1))
+}
+
+/// What the grid needs to know about an edge to bound its influence on the distance.
+struct EdgeInfo {
+    bounds: Bounds,
+    start: Vec2,
+    end: Vec2,
+    color: Color,
+    /// Directions of the edge at its start and end, normalized.
+    start_dir: Vec2,
+    end_dir: Vec2,
+    /// Bisectors of the corners at the start and end of the edge, as computed by the edge selectors.
+    start_bisector: Vec2,
+    end_bisector: Vec2
+}
+
+impl EdgeInfo {
+    fn new(contour: &Contour, i: usize) -> Self {
+        let len = contour.edges.len();
+        let edge = &contour.edges[i];
+        let prev = &contour.edges[(i + len - 1) % len];
+        let next = &contour.edges[(i + 1) % len];
+
+        let start_dir = edge.segment.direction(0.0).normalize();
+        let end_dir = edge.segment.direction(1.0).normalize();
+        EdgeInfo {
+            bounds: edge.segment.bounds(),
+            start: edge.segment.start(),
+            end: edge.segment.end(),
+            color: edge.color,
+            start_dir,
+            end_dir,
+            start_bisector: (prev.segment.direction(1.0).normalize() + start_dir).normalize(),
+            end_bisector: (end_dir + next.segment.direction(0.0).normalize()).normalize()
+        }
+    }
+}
+
+fn corners(cell: Bounds) -> [Vec2; 4] {
+    [
+        vec2(cell.x_min, cell.y_min),
+        vec2(cell.x_max, cell.y_min),
+        vec2(cell.x_min, cell.y_max),
+        vec2(cell.x_max, cell.y_max)
+    ]
+}
+
+fn max_distance(cell: Bounds, p: Vec2) -> Float {
+    corners(cell).iter().map(|&c| (c - p).length()).fold(0.0, Float::max)
+}
+
+fn min_distance(cell: Bounds, b: Bounds) -> Float {
+    let dx = (b.x_min - cell.x_max).max(cell.x_min - b.x_max).max(0.0);
+    let dy = (b.y_min - cell.y_max).max(cell.y_min - b.y_max).max(0.0);
+    vec2(dx, dy).length()
+}
+
+/// Lower bound of the perpendicular distance an edge selector can measure from a point of the cell,
+/// to the extension of an edge past its endpoint `p` going in direction `dir`.
+/// It's only measured in the wedge in front of the bisector of the corner and behind the endpoint.
+fn extension_distance(cell: Bounds, p: Vec2, dir: Vec2, bisector: Vec2, margin: Float) -> Float {
+    let corners = corners(cell);
+    let behind = corners.iter().any(|&c| (c - p).dot(dir) > -margin);
+    let in_front = corners.iter().any(|&c| (c - p).dot(bisector) > -margin);
+    if !(behind && in_front) {
+        return Float::MAX;
+    }
+
+    let sides = corners.map(|c| (c - p).cross(dir));
+    let (min, max) = sides.iter().fold((Float::MAX, Float::MIN), |(min, max), &s| (min.min(s), max.max(s)));
+    if min <= 0.0 && max >= 0.0 { 0.0 } else { min.abs().min(max.abs()) }
+}
+
+/// A uniform grid over the area where distances are queried,
+/// storing for every cell the edges of each contour that can affect the distance of a point in the cell.
+///
+/// An edge is left out of a cell when, for every channel of its color, both its bounding box
+/// and the extensions of its endpoints are farther away from the cell than some other edge
+/// of the same contour is at most. Edge selectors would ignore it for every point of the cell,
+/// so the distances are exactly the same as when going through every edge.
+pub struct EdgeGrid {
+    region: Bounds,
+    columns: usize,
+    rows: usize,
+    contour_count: usize,
+    /// For every cell and contour, the range of their edges in `edges`.
+    cells: Vec<Range<usize>>,
+    edges: Vec<usize>
+}
+
+impl EdgeGrid {
+    /// Builds a grid of `columns` by `rows` cells over `region`, which must contain every queried point.
+    pub fn new(contours: &[Contour], region: Bounds, columns: usize, rows: usize) -> Self {
+        let (columns, rows) = (columns.max(1), rows.max(1));
+        let infos: Vec<Vec<EdgeInfo>> = contours.iter()
+            .map(|c| (0..c.edges.len()).map(|i| EdgeInfo::new(c, i)).collect())
+            .collect();
+
+        let cell_width = region.width()/columns as Float;
+        let cell_height = region.height()/rows as Float;
+        // cells are slightly enlarged, for points rounded into the neighbouring cell,
+        // and distances are compared with some tolerance for rounding errors
+        let slack = 0.01*cell_width.max(cell_height);
+        let magnitude = region.x_min.abs().max(region.x_max.abs()).max(region.y_min.abs()).max(region.y_max.abs());
+        let margin = slack + 1e-4*magnitude;
+
+        let mut cells = Vec::with_capacity(columns*rows*contours.len());
+        let mut edges = vec![];
+        for row in 0..rows {
+            for column in 0..columns {
+                let cell = Bounds {
+                    x_min: region.x_min + column as Float*cell_width - slack,
+                    y_min: region.y_min + row as Float*cell_height - slack,
+                    x_max: region.x_min + (column + 1) as Float*cell_width + slack,
+                    y_max: region.y_min + (row + 1) as Float*cell_height + slack
+                };
+
+                for contour in &infos {
+                    // how far the nearest edge of every channel can be from a point of the cell
+                    let mut upper = [Float::MAX; 3];
+                    for info in contour {
+                        let distance = max_distance(cell, info.start).min(max_distance(cell, info.end));
+                        for (c, channel) in [Color::RED, Color::GREEN, Color::BLUE].into_iter().enumerate() {
+                            if info.color.contains(channel) {
+                                upper[c] = upper[c].min(distance);
+                            }
+                        }
+                    }
+
+                    let first = edges.len();
+                    for i in visit_order(contour.len()) {
+                        let info = &contour[i];
+                        let lower = min_distance(cell, info.bounds)
+                            .min(extension_distance(cell, info.start, -info.start_dir, info.start_bisector, margin))
+                            .min(extension_distance(cell, info.end, info.end_dir, -info.end_bisector, margin));
+
+                        // black edges don't bound any channel, but single-channel fields still measure them
+                        let relevant = info.color.is_empty() || [Color::RED, Color::GREEN, Color::BLUE].into_iter().enumerate()
+                            .any(|(c, channel)| info.color.contains(channel) && lower <= upper[c] + margin);
+                        if relevant {
+                            edges.push(i);
+                        }
+                    }
+                    cells.push(first..edges.len());
+                }
+            }
+        }
+
+        EdgeGrid { region, columns, rows, contour_count: contours.len(), cells, edges }
+    }
+
+    /// Returns the edges of the contour that can affect the distance at `p`, in visiting order.
+    pub fn edges(&self, p: Vec2, contour: usize) -> &[usize] {
+        let column = ((p.x - self.region.x_min)/self.region.width()*self.columns as Float) as usize;
+        let row = ((p.y - self.region.y_min)/self.region.height()*self.rows as Float) as usize;
+        let cell = row.min(self.rows - 1)*self.columns + column.min(self.columns - 1);
+        &self.edges[self.cells[cell*self.contour_count + contour].clone()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sdf::{consts::PI, vec2, CubicDistance, EdgeColoring, Float, GeneratorConfig, Shape, ShapeBuilder};
+
+    fn shapes() -> Vec<Shape> {
+        let mut donut = ShapeBuilder::new();
+        donut.rounded_rect(vec2(0.0, 0.0), vec2(100.0, 80.0), 20.0);
+        let hole = [vec2(30.0, 20.0), vec2(70.0, 20.0), vec2(70.0, 60.0), vec2(30.0, 60.0)];
+        donut.polygon(&hole);
+
+        let mut overlapping = ShapeBuilder::new();
+        overlapping.circle(vec2(30.0, 30.0), 30.0).ellipse(vec2(60.0, 40.0), vec2(40.0, 20.0));
+
+        let mut star = ShapeBuilder::new();
+        let points: Vec<_> = (0..10).map(|i| {
+            let (sin, cos) = (i as Float*PI/5.0).sin_cos();
+            let r = if i % 2 == 0 { 50.0 } else { 20.0 };
+            vec2(50.0 + r*sin, 50.0 - r*cos)
+        }).collect();
+        star.polygon(&points);
+
+        vec![
+            donut.build(),
+            overlapping.build(),
+            star.build(),
+            Shape::from_svg_path("M10 90 Q10 10 50 10 T90 90 C70 60 30 120 10 90 Z M40 50 c10 -10 20 10 5 15 s-15 0 -5 -15 z").unwrap(),
+            Shape::from_svg_path("M0 0 C100 0 0 100 100 100 C0 100 100 0 0 0 Z").unwrap()
+        ]
+    }
+
+    #[test]
+    fn accelerated_distances_match_brute_force() {
+        for (i, shape) in shapes().into_iter().enumerate() {
+            for (overlap_support, cubic_distance) in [(true, CubicDistance::Fast), (false, CubicDistance::Exact { tolerance: 1e-4 })] {
+                let fast = GeneratorConfig { overlap_support, cubic_distance, ..Default::default() };
+                let brute = GeneratorConfig { acceleration: false, ..fast };
+
+                for font_size in [16.0, 70.0] {
+                    let placement = shape.placement(100.0, font_size, 3.0);
+                    let generate_sdf = |config| {
+                        let mut pixels = vec![];
+                        shape.generate_sdf(&placement, config, |p, d| pixels.push((p, d)));
+                        shape.generate_psdf(&placement, config, |p, d| pixels.push((p, d)));
+                        pixels
+                    };
+                    assert!(generate_sdf(&fast) == generate_sdf(&brute), "shape {i} at {font_size}px");
+
+                    let coloured = shape.clone().color_edges(EdgeColoring::Simple, 3.0, 0);
+                    let placement = coloured.placement(100.0, font_size, 3.0);
+                    let generate_mtsdf = |config| {
+                        let mut pixels = vec![];
+                        coloured.generate_mtsdf(&placement, config, |p, d| pixels.push((p, d)));
+                        pixels
+                    };
+                    assert!(generate_mtsdf(&fast) == generate_mtsdf(&brute), "coloured shape {i} at {font_size}px");
+                }
+            }
+        }
+    }
+}
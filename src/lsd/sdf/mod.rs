@@ -0,0 +1,335 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use bitflags::bitflags;
+use image::GenericImage;
+use ttf_parser::{Face, GlyphId, OutlineBuilder, Rect};
+
+/// The floating point type of the geometry and of the distances, `f64` with the `f64` feature.
+/// Images are always stored as `f32`.
+#[cfg(not(feature = "f64"))]
+pub type Float = f32;
+#[cfg(feature = "f64")]
+pub type Float = f64;
+
+#[cfg(not(feature = "f64"))]
+use std::f32::consts;
+#[cfg(feature = "f64")]
+use std::f64::consts;
+
+/// Converts a distance to `f32`, to store it in an image.
+#[cfg(not(feature = "f64"))]
+fn to_f32(v: Float) -> f32 { v }
+#[cfg(feature = "f64")]
+fn to_f32(v: Float) -> f32 { v as f32 }
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Vec2 { pub x: Float, pub y: Float }
+
+impl std::ops::Add for Vec2 {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output { Vec2 { x: self.x + rhs.x, y: self.y + rhs.y } }
+}
+
+impl std::ops::Sub for Vec2 {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output { Vec2 { x: self.x - rhs.x, y: self.y - rhs.y } }
+}
+
+impl std::ops::Neg for Vec2 {
+    type Output = Self;
+    fn neg(self) -> Self::Output { Vec2 { x: -self.x, y: -self.y } }
+}
+
+impl std::ops::Mul<Float> for Vec2 {
+    type Output = Vec2;
+    fn mul(self, rhs: Float) -> Self::Output { Vec2 { x: self.x * rhs, y: self.y * rhs } }
+}
+
+impl std::ops::Mul<Vec2> for Float {
+    type Output = Vec2;
+    fn mul(self, rhs: Vec2) -> Self::Output { rhs * self }
+}
+
+impl Vec2 {
+    /// Returns an orthogonal vector
+    /// Orthogonal in the counter clockwise direction
+    /// if the parameter is true, and in the clockwise direction if it's false.
+    pub fn orthogonal(self, counter_clockwise: bool) -> Vec2 {
+        if counter_clockwise { vec2(-self.y, self.x) } else { vec2(self.y, -self.x) }
+    }
+
+    pub fn dot(self, other: Vec2) -> Float {
+        self.x * other.x + self.y * other.y
+    }
+
+    pub fn cross(self, other: Vec2) -> Float {
+        self.x*other.y - self.y*other.x
+    }
+
+    pub fn length_sqr(self) -> Float { self.x*self.x + self.y*self.y }
+    pub fn length(self) -> Float { self.length_sqr().sqrt() }
+    pub fn normalize(self) -> Vec2 {
+        let l = self.length();
+        self * (1.0 / l)
+    }
+
+    pub fn shoelace(self, other: Vec2) -> Float {
+        (other.x - self.x)*(self.y + other.y)
+    }
+}
+
+pub fn vec2(x: Float, y: Float) -> Vec2 { Vec2 { x, y } }
+
+pub fn lerp<T>(a: T, b: T, t: Float) -> T
+    where T: Copy + std::ops::Add<T, Output = T> + std::ops::Sub<T, Output = T> + std::ops::Mul<Float, Output = T>
+{
+    a + (b - a)*t
+}
+
+
+bitflags! {
+    /// The set of channels an edge contributes to.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct Color: u8 {
+        const BLACK = 0;
+        const RED = 1;
+        const GREEN = 2;
+        const YELLOW = 3;
+        const BLUE = 4;
+        const MAGENTA = 5;
+        const CYAN = 6;
+        const WHITE = 7;
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SignedDistance {
+    pub dist: Float,
+    pub dot: Float
+}
+
+impl SignedDistance {
+    /// The distance of a selector that hasn't found any edge yet.
+    /// It is negative so that such a selector is considered to be outside of the shape.
+    pub const INFINITE: Self = SignedDistance { dist: -Float::MAX, dot: 0.0 };
+}
+
+impl PartialOrd for SignedDistance {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        self.dist.abs().partial_cmp(&other.dist.abs())
+            .map(|c| c.then(self.dot.partial_cmp(&other.dot).unwrap_or(std::cmp::Ordering::Equal)))
+    }
+}
+
+/// An axis-aligned bounding box, in shape units.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bounds {
+    pub x_min: Float,
+    pub y_min: Float,
+    pub x_max: Float,
+    pub y_max: Float
+}
+
+impl Bounds {
+    /// Bounds that don't contain any point, and grow to the first included point.
+    pub const EMPTY: Self = Bounds { x_min: Float::MAX, y_min: Float::MAX, x_max: Float::MIN, y_max: Float::MIN };
+
+    pub fn width(&self) -> Float { self.x_max - self.x_min }
+    pub fn height(&self) -> Float { self.y_max - self.y_min }
+
+    pub fn is_empty(&self) -> bool {
+        self.x_min > self.x_max || self.y_min > self.y_max
+    }
+
+    /// Grows the bounds to contain the given point.
+    pub fn include(&mut self, p: Vec2) {
+        self.x_min = self.x_min.min(p.x);
+        self.y_min = self.y_min.min(p.y);
+        self.x_max = self.x_max.max(p.x);
+        self.y_max = self.y_max.max(p.y);
+    }
+
+    /// Grows the bounds to contain the given bounds.
+    pub fn include_bounds(&mut self, other: Bounds) {
+        self.x_min = self.x_min.min(other.x_min);
+        self.y_min = self.y_min.min(other.y_min);
+        self.x_max = self.x_max.max(other.x_max);
+        self.y_max = self.y_max.max(other.y_max);
+    }
+}
+
+impl From<Rect> for Bounds {
+    fn from(rect: Rect) -> Self {
+        Bounds { x_min: rect.x_min as Float, y_min: rect.y_min as Float, x_max: rect.x_max as Float, y_max: rect.y_max as Float }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Edge { 
+    pub segment: Segment,
+    pub color: Color
+}
+
+/// A closed loop of edges.
+#[derive(Debug, Clone)]
+pub struct Contour {
+    pub edges: Vec<Edge>
+}
+
+impl Contour {
+    pub fn new(edges: Vec<Edge>) -> Self {
+        Contour { edges }
+    }
+
+    /// Reverses the direction of the contour, flipping its winding.
+    pub fn reverse(&mut self) {
+        self.edges.reverse();
+        for edge in &mut self.edges {
+            edge.segment = edge.segment.reverse();
+        }
+    }
+
+    /// Returns the winding number of the contour, 1 or -1
+    /// Returns 0 if the contour has no edges
+    pub fn winding(&self) -> i32 {
+        let total = match self.edges.len() {
+            0 => return 0,
+            1 => {
+                let seg = self.edges[0].segment;
+                let (a, b, c) = (seg.sample(0.0), seg.sample(1.0/3.0), seg.sample(2.0/3.0));
+                a.shoelace(b) + b.shoelace(c) + c.shoelace(a)
+            }
+            2 => {
+                let (sega, segb) = (self.edges[0].segment, self.edges[1].segment);
+                let (a, b, c, d) = (sega.sample(0.0), sega.sample(0.5), segb.sample(0.0), segb.sample(0.5));
+                a.shoelace(b) + b.shoelace(c) + c.shoelace(d) + d.shoelace(a)
+            }
+            _ => {
+                let mut sum = 0.0;
+                let mut prev = self.edges.last().unwrap().segment.sample(0.0);
+                for edge in &self.edges {
+                    let p = edge.segment.sample(0.0);
+                    sum += prev.shoelace(p);
+                    prev = p;
+                }
+                sum
+            }
+
+        };
+        
+        total.signum() as i32
+    }
+}
+
+mod atlas;
+mod build;
+mod segment;
+mod shape;
+mod render;
+mod correction;
+mod grid;
+mod projection;
+mod quantize;
+mod scanline;
+mod svg;
+mod transform;
+mod validation;
+
+pub use atlas::{AtlasAllocation, AtlasBuilder, MAX_PAGE_SIZE};
+pub use build::ShapeBuilder;
+pub use segment::{CubicDistance, Segment};
+pub use shape::{Shape, ColouredShape, EdgeColoring};
+pub use render::{MultiDistance, DistanceRange, GeneratorConfig, one_shot_distance};
+pub use projection::{Placement, Projection};
+pub use quantize::{ChannelLayout, Quantized, QuantizedPixel};
+pub use svg::{SvgError, SvgPathError};
+pub use transform::Transform;
+pub use validation::{ShapeError, ValidationMode};
+pub use scanline::{FillRule, Intersection, Scanline};
+pub use correction::{correct_errors, ErrorCorrectionConfig, ErrorCorrectionMode};
+
+/// An atlas of glyphs, with what is needed to render them.
+pub struct Mtsdf {
+    /// The images of every page of the atlas, with the MTSDF of every glyph, see [`ColouredShape::generate_mtsdf`].
+    pub pages: Vec<image::Rgba32FImage>,
+    pub atlas: AtlasBuilder,
+    pub glyphs: HashMap<char, AtlasGlyph>,
+    /// Characters whose glyph is larger than the maximum size of a page, which are missing from `glyphs`.
+    pub too_large: Vec<char>,
+    /// The font size the glyphs were generated at.
+    pub font_size: Float,
+    /// The width, in pixels of the atlas, of the distance range mapped to the values 0.0 to 1.0, see [`DistanceRange`].
+    pub range_px: Float
+}
+
+/// A glyph of an [`Mtsdf`] atlas.
+#[derive(Debug, Clone, Copy)]
+pub struct AtlasGlyph {
+    /// The page and rectangle of the atlas the glyph was written to.
+    pub allocation: AtlasAllocation,
+    /// How the glyph is projected in its rectangle of the atlas, starting at the top-left corner of the rectangle.
+    pub placement: Placement
+}
+
+/// Generates an atlas of the glyphs of the font, with pages of at most `max_texture_size` pixels on each side,
+/// and at most [`MAX_PAGE_SIZE`].
+pub fn generate_mtsdf(face: &Face, max_texture_size: u32) -> Mtsdf {
+    let mut atlas = AtlasBuilder::new((256, 256), (max_texture_size, max_texture_size));
+
+    let font_size = 50.0;
+    let padding = 2.0;
+    let units_per_em = face.units_per_em() as Float;
+    let config = GeneratorConfig { range: DistanceRange::Px(4.0), ..Default::default() };
+
+    let mut glyphs = vec![];
+    let mut placements = HashMap::new();
+    let mut too_large = vec![];
+    for c in ('A'..='Z').chain('0'..='9').chain('a'..='z').chain('*'..='*') {
+        let Some(id) = face.glyph_index(c) else { continue };
+        let Ok(shape) = Shape::from_glyph(face, id, ValidationMode::Repair) else { continue };
+
+        let coloured = shape.color_edges(EdgeColoring::Simple, 2.0, 0);
+
+        let placement = coloured.placement(units_per_em, font_size, padding);
+        let Some(allocation) = atlas.allocate(placement.width, placement.height) else {
+            too_large.push(c);
+            continue
+        };
+        placements.insert(c, AtlasGlyph { allocation, placement });
+        glyphs.push((coloured, placement, allocation));
+    }
+
+    // glyphs are taken by the first available thread, each one being written to its own place in the atlas
+    let next_glyph = AtomicUsize::new(0);
+    let rendered: Vec<Vec<(usize, image::Rgba32FImage)>> = std::thread::scope(|scope| {
+        let handles: Vec<_> = (0..render::thread_count(0)).map(|_| scope.spawn(|| {
+            let mut rendered = vec![];
+            loop {
+                let i = next_glyph.fetch_add(1, Ordering::Relaxed);
+                let Some((coloured, placement, _)) = glyphs.get(i) else { break };
+                rendered.push((i, render_glyph(coloured, placement, &config)));
+            }
+            rendered
+        })).collect();
+        handles.into_iter().map(|h| h.join().unwrap()).collect()
+    });
+
+    let mut pages: Vec<_> = (0..atlas.pages().len()).map(|page| {
+        let (width, height) = atlas.page_size(page);
+        image::Rgba32FImage::new(width, height)
+    }).collect();
+    for (i, glyph) in rendered.into_iter().flatten() {
+        let allocation = glyphs[i].2;
+        let offset = allocation.rectangle.min;
+        pages[allocation.page].copy_from(&glyph, offset.x as u32, offset.y as u32).unwrap();
+    }
+
+    Mtsdf { pages, atlas, glyphs: placements, too_large, font_size, range_px: config.range.to_pixels(font_size) }
+}
+
+fn render_glyph(coloured: &ColouredShape, placement: &Placement, config: &GeneratorConfig) -> image::Rgba32FImage {
+    let mut image = image::Rgba32FImage::new(placement.width, placement.height);
+    coloured.write_mtsdf(placement, config, &mut image, (0, 0));
+    image
+}
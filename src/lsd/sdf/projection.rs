@@ -0,0 +1,77 @@
+use super::{vec2, Bounds, Float, Vec2};
+
+/// Maps shape units to the pixels of a generated image: `pixel = (shape + translate)*scale`.
+/// Pixels are measured from the bottom-left corner of the image, with the Y axis going up like in the shape.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Projection {
+    /// Pixels per shape unit.
+    pub scale: Float,
+    /// Offset applied before scaling, in shape units.
+    pub translate: Vec2
+}
+
+impl Projection {
+    pub fn new(scale: Float, translate: Vec2) -> Self {
+        Projection { scale, translate }
+    }
+
+    /// Converts a point from shape units to pixels.
+    pub fn project(&self, p: Vec2) -> Vec2 {
+        (p + self.translate)*self.scale
+    }
+
+    /// Converts a point from pixels to shape units.
+    pub fn unproject(&self, p: Vec2) -> Vec2 {
+        p*(1.0/self.scale) - self.translate
+    }
+}
+
+/// Where a shape lands in a generated image.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Placement {
+    pub projection: Projection,
+    /// Size of the image, in pixels.
+    pub width: u32,
+    pub height: u32,
+    /// The size of an em in the units of the shape, which is [`ttf_parser::Face::units_per_em`] for glyphs.
+    pub units_per_em: Float
+}
+
+impl Placement {
+    /// Places a shape with the given bounds at the given font size, with at least `padding` pixels around it.
+    ///
+    /// Every shape placed at the same font size has the same scale, and its origin on the corner of a pixel,
+    /// so that glyphs line up exactly when rendered.
+    pub fn new(bounds: Bounds, units_per_em: Float, font_size_px: Float, padding: Float) -> Self {
+        let bounds = if bounds.is_empty() { Bounds { x_min: 0.0, y_min: 0.0, x_max: 0.0, y_max: 0.0 } } else { bounds };
+        let scale = font_size_px/units_per_em;
+
+        let left = (bounds.x_min*scale - padding).floor();
+        let bottom = (bounds.y_min*scale - padding).floor();
+        let right = (bounds.x_max*scale + padding).ceil();
+        let top = (bounds.y_max*scale + padding).ceil();
+
+        Placement {
+            projection: Projection::new(scale, vec2(-left, -bottom)*(1.0/scale)),
+            width: (right - left) as u32,
+            height: (top - bottom) as u32,
+            units_per_em
+        }
+    }
+
+    pub fn font_size_px(&self) -> Float {
+        self.projection.scale*self.units_per_em
+    }
+
+    /// Returns the position of the origin of the shape, in pixels from the bottom-left corner of the image.
+    /// For glyphs, this is where the baseline starts.
+    pub fn origin(&self) -> Vec2 {
+        self.projection.project(vec2(0.0, 0.0))
+    }
+
+    /// Returns the position of the center of a pixel in shape units,
+    /// with image coordinates going down from the top-left corner.
+    pub fn pixel_center(&self, x: u32, y: u32) -> Vec2 {
+        self.projection.unproject(vec2(x as Float + 0.5, (self.height - y) as Float - 0.5))
+    }
+}
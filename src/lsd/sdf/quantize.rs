@@ -0,0 +1,93 @@
+use image::{ImageBuffer, Luma, LumaA, Pixel, Rgb, Rgba};
+
+use super::{projection::Placement, render::GeneratorConfig, shape::{ColouredShape, Shape}, Float};
+
+/// What is stored in the channels of a quantized image, which depends on its pixel type.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ChannelLayout {
+    /// The multi-channel distance, in [`Rgb`] pixels.
+    Rgb,
+    /// The multi-channel distance in RGB, and the true distance in alpha, as an MTSDF, in [`Rgba`] pixels.
+    #[default]
+    Rgba,
+    /// The single-channel distance (median of the multi-channel distance), in [`Luma`] pixels.
+    R,
+    /// The single-channel distance, and the true distance in alpha, in [`LumaA`] pixels.
+    RA
+}
+
+/// A channel type of quantized images.
+pub trait Quantized: image::Primitive {
+    /// Converts a pixel value, clamped to the distance range (0.0 to 1.0), to the nearest representable value.
+    fn quantize(value: Float) -> Self;
+}
+
+impl Quantized for u8 {
+    fn quantize(value: Float) -> Self {
+        (value.clamp(0.0, 1.0)*u8::MAX as Float).round() as u8
+    }
+}
+
+impl Quantized for u16 {
+    fn quantize(value: Float) -> Self {
+        (value.clamp(0.0, 1.0)*u16::MAX as Float).round() as u16
+    }
+}
+
+/// A pixel type of quantized images, and what is stored in its channels.
+pub trait QuantizedPixel: Pixel<Subpixel: Quantized> {
+    const LAYOUT: ChannelLayout;
+}
+
+impl<S: Quantized> QuantizedPixel for Rgb<S> where Rgb<S>: Pixel<Subpixel = S> {
+    const LAYOUT: ChannelLayout = ChannelLayout::Rgb;
+}
+
+impl<S: Quantized> QuantizedPixel for Rgba<S> where Rgba<S>: Pixel<Subpixel = S> {
+    const LAYOUT: ChannelLayout = ChannelLayout::Rgba;
+}
+
+impl<S: Quantized> QuantizedPixel for Luma<S> where Luma<S>: Pixel<Subpixel = S> {
+    const LAYOUT: ChannelLayout = ChannelLayout::R;
+}
+
+impl<S: Quantized> QuantizedPixel for LumaA<S> where LumaA<S>: Pixel<Subpixel = S> {
+    const LAYOUT: ChannelLayout = ChannelLayout::RA;
+}
+
+impl Shape {
+    /// Generates a single-channel SDF of the glyph into an image with integer channels, like [`image::GrayImage`],
+    /// with the top-left corner of the glyph placed at `offset`.
+    ///
+    /// Values are rounded like in [`ColouredShape::write_quantized`].
+    pub fn write_quantized<S: Quantized>(&self, placement: &Placement, config: &GeneratorConfig, image: &mut ImageBuffer<Luma<S>, Vec<S>>, offset: (u32, u32))
+        where Luma<S>: Pixel<Subpixel = S>
+    {
+        self.generate_sdf(placement, config, |(x, y), d| {
+            image.put_pixel(offset.0 + x, offset.1 + y, Luma([S::quantize(d)]));
+        });
+    }
+}
+
+impl ColouredShape {
+    /// Generates the glyph into an image with integer channels, like [`image::RgbaImage`] or `ImageBuffer<Luma<u16>, Vec<u16>>`,
+    /// with the top-left corner of the glyph placed at `offset`. What is generated depends on the pixel type, see [`ChannelLayout`].
+    ///
+    /// Values are rounded to the nearest integer, after being clamped to [`GeneratorConfig::range`],
+    /// so the image can be uploaded as an `R8G8B8A8_UNORM` or `R16_UNORM` texture for example.
+    pub fn write_quantized<P: QuantizedPixel>(&self, placement: &Placement, config: &GeneratorConfig, image: &mut ImageBuffer<P, Vec<P::Subpixel>>, offset: (u32, u32)) {
+        let median = |r: Float, g: Float, b: Float| r.min(g).max(r.max(g).min(b));
+        // only the first channels are used by pixels with less than four channels
+        let mut put = |(x, y), channels: [Float; 4]| {
+            let channels = channels.map(P::Subpixel::quantize);
+            image.put_pixel(offset.0 + x, offset.1 + y, *P::from_slice(&channels[..P::CHANNEL_COUNT as usize]));
+        };
+
+        match P::LAYOUT {
+            ChannelLayout::Rgb => self.generate_msdf(placement, config, |p, [r, g, b]| put(p, [r, g, b, 0.0])),
+            ChannelLayout::R => self.generate_msdf(placement, config, |p, [r, g, b]| put(p, [median(r, g, b), 0.0, 0.0, 0.0])),
+            ChannelLayout::Rgba => self.generate_mtsdf(placement, config, put),
+            ChannelLayout::RA => self.generate_mtsdf(placement, config, |p, [r, g, b, a]| put(p, [median(r, g, b), a, 0.0, 0.0]))
+        }
+    }
+}
@@ -0,0 +1,743 @@
+use image::{GenericImage, Pixel};
+
+use super::{grid::{visit_order, EdgeGrid}, projection::Placement, scanline::{FillRule, Scanline}, segment::CubicDistance, shape::{ColouredShape, Shape}, Color, Bounds, Contour, Edge, Float, SignedDistance, Vec2, to_f32};
+
+#[derive(Clone, Copy, PartialEq)]
+pub struct MultiDistance {
+    pub r: Float,
+    pub g: Float,
+    pub b: Float,
+    pub a: Float
+}
+
+impl MultiDistance {
+    fn resolve(&self) -> Float{
+        self.r.min(self.g).max(self.r.max(self.g).min(self.b))
+    }
+}
+
+/// Accumulates the distance from a point to every edge of a shape.
+trait EdgeSelector: Clone {
+    type Distance: Copy;
+
+    fn new() -> Self;
+    fn add_edge(&mut self, point: Vec2, prev_edge: &Edge, edge: &Edge, next_edge: &Edge, cubic_distance: CubicDistance);
+    fn merge(&mut self, other: &Self);
+    fn distance(&self, point: Vec2) -> Self::Distance;
+    /// Reduces a distance to the single signed distance it represents when rendered.
+    fn resolve(distance: &Self::Distance) -> Float;
+}
+
+/// Selects the true (euclidean) signed distance to the nearest edge.
+#[derive(Clone)]
+struct TrueEdgeSelector {
+    min_distance: SignedDistance
+}
+
+impl EdgeSelector for TrueEdgeSelector {
+    type Distance = Float;
+
+    fn new() -> Self {
+        Self { min_distance: SignedDistance::INFINITE }
+    }
+
+    fn add_edge(&mut self, point: Vec2, _prev_edge: &Edge, edge: &Edge, _next_edge: &Edge, cubic_distance: CubicDistance) {
+        let (dist, _) = edge.segment.signed_distance(point, cubic_distance);
+        if dist < self.min_distance {
+            self.min_distance = dist;
+        }
+    }
+
+    fn merge(&mut self, other: &Self) {
+        if other.min_distance < self.min_distance {
+            self.min_distance = other.min_distance;
+        }
+    }
+
+    fn distance(&self, _point: Vec2) -> Float {
+        self.min_distance.dist
+    }
+
+    fn resolve(distance: &Float) -> Float {
+        *distance
+    }
+}
+
+#[derive(Clone)]
+struct PerpEdgeSelector {
+    min_true_distance: SignedDistance,
+    min_negative_perp_dist: Float,
+    min_positive_perp_dist: Float,
+    near_edge: Option<Edge>,
+    near_edge_t: Float
+}
+
+/// Returns true if `distance` was modified and false otherwise
+fn get_perpendicular_distance(distance: &mut Float, ep: Vec2, edge_dir: Vec2) -> bool {
+    let ts = ep.dot(edge_dir);
+    if ts > 0.0 {
+        let perp_distance = ep.cross(edge_dir);
+        if perp_distance.abs() < distance.abs() {
+            *distance = perp_distance;
+            return true;
+        }
+    }
+    false
+}
+
+impl PerpEdgeSelector {
+    fn add_edge_true_distance(&mut self, edge: &Edge, dist: SignedDistance, t: Float) {
+        if dist < self.min_true_distance {
+            self.min_true_distance = dist;
+            self.near_edge = Some(*edge);
+            self.near_edge_t = t;
+        }
+    }
+
+    fn add_edge_perp_distance(&mut self, dist: Float) {
+        if dist <= 0.0 && dist > self.min_negative_perp_dist {
+            self.min_negative_perp_dist = dist;
+        } else if dist >= 0.0 && dist < self.min_positive_perp_dist {
+            self.min_positive_perp_dist = dist;
+        }
+    }
+
+    fn true_distance(&self) -> SignedDistance {
+        self.min_true_distance
+    }
+}
+
+impl EdgeSelector for PerpEdgeSelector {
+    type Distance = Float;
+
+    fn new() -> Self {
+        Self {
+            min_true_distance: SignedDistance::INFINITE,
+            min_positive_perp_dist: Float::MAX,
+            min_negative_perp_dist: Float::MIN,
+            near_edge: None,
+            near_edge_t: 0.0
+        }
+    }
+
+    fn add_edge(&mut self, point: Vec2, prev_edge: &Edge, edge: &Edge, next_edge: &Edge, cubic_distance: CubicDistance) {
+        let (distance, t) = edge.segment.signed_distance(point, cubic_distance);
+        self.add_edge_true_distance(edge, distance, t);
+
+        let ap = point - edge.segment.sample(0.0);
+        let bp = point - edge.segment.sample(1.0);
+        let a_dir = edge.segment.direction(0.0).normalize();
+        let b_dir = edge.segment.direction(1.0).normalize();
+
+        let prev_dir = prev_edge.segment.direction(1.0).normalize();
+        let next_dir = next_edge.segment.direction(0.0).normalize();
+
+        let add = ap.dot((prev_dir + a_dir).normalize());
+        let bdd = -bp.dot((b_dir + next_dir).normalize());
+
+        if add > 0.0 {
+            let mut pd = distance.dist;
+            if get_perpendicular_distance(&mut pd, ap, -a_dir) {
+                pd = -pd;
+                self.add_edge_perp_distance(pd);
+            }
+        }
+        if bdd > 0.0 {
+            let mut pd = distance.dist;
+            if get_perpendicular_distance(&mut pd, bp, b_dir) {
+                self.add_edge_perp_distance(pd);
+            }
+        }
+    }
+
+    fn distance(&self, point: Vec2) -> Float {
+        let min_distance = if self.min_true_distance.dist < 0.0 { self.min_negative_perp_dist } else { self.min_positive_perp_dist };
+
+        if let Some(edge) = self.near_edge {
+            let distance = self.min_true_distance;
+            let distance = edge.segment.distance_to_perp_dist(distance, point, self.near_edge_t);
+            if distance.dist.abs() < min_distance.abs() {
+                return distance.dist;
+            }
+        }
+
+        min_distance
+    }
+
+    fn merge(&mut self, other: &Self) {
+        if other.min_true_distance < self.min_true_distance {
+            self.min_true_distance = other.min_true_distance;
+            self.near_edge = other.near_edge;
+            self.near_edge_t = other.near_edge_t;
+        }
+
+        if other.min_negative_perp_dist > self.min_negative_perp_dist {
+            self.min_negative_perp_dist = other.min_negative_perp_dist;
+        }
+        if other.min_positive_perp_dist < self.min_positive_perp_dist {
+            self.min_positive_perp_dist = other.min_positive_perp_dist;
+        }
+    }
+
+    fn resolve(distance: &Float) -> Float {
+        *distance
+    }
+}
+
+#[derive(Clone)]
+struct MTEdgeSelector {
+    r: PerpEdgeSelector,
+    g: PerpEdgeSelector,
+    b: PerpEdgeSelector
+}
+
+impl EdgeSelector for MTEdgeSelector {
+    type Distance = MultiDistance;
+
+    fn new() -> Self {
+        Self {
+            r: PerpEdgeSelector::new(),
+            g: PerpEdgeSelector::new(),
+            b: PerpEdgeSelector::new(),
+        }
+    }
+
+    fn add_edge(&mut self, point: Vec2, prev_edge: &Edge, edge: &Edge, next_edge: &Edge, cubic_distance: CubicDistance) {
+        let (dist, t) = edge.segment.signed_distance(point, cubic_distance);
+        if edge.color.contains(Color::RED) { self.r.add_edge_true_distance(edge, dist, t); }
+        if edge.color.contains(Color::GREEN) { self.g.add_edge_true_distance(edge, dist, t); }
+        if edge.color.contains(Color::BLUE) { self.b.add_edge_true_distance(edge, dist, t); }
+
+        let ap = point - edge.segment.sample(0.0);
+        let bp = point - edge.segment.sample(1.0);
+        let a_dir = edge.segment.direction(0.0).normalize();
+        let b_dir = edge.segment.direction(1.0).normalize();
+
+        let prev_dir = prev_edge.segment.direction(1.0).normalize();
+        let next_dir = next_edge.segment.direction(0.0).normalize();
+
+        let add = ap.dot((prev_dir + a_dir).normalize());
+        let bdd = -bp.dot((b_dir + next_dir).normalize());
+
+        if add > 0.0 {
+            let mut pd = dist.dist;
+            if get_perpendicular_distance(&mut pd, ap, -a_dir) {
+                pd = -pd;
+                if edge.color.contains(Color::RED) { self.r.add_edge_perp_distance(pd); }
+                if edge.color.contains(Color::GREEN) { self.g.add_edge_perp_distance(pd); }
+                if edge.color.contains(Color::BLUE) { self.b.add_edge_perp_distance(pd); }
+            }
+        }
+        if bdd > 0.0 {
+            let mut pd = dist.dist;
+            if get_perpendicular_distance(&mut pd, bp, b_dir) {
+                if edge.color.contains(Color::RED) { self.r.add_edge_perp_distance(pd); }
+                if edge.color.contains(Color::GREEN) { self.g.add_edge_perp_distance(pd); }
+                if edge.color.contains(Color::BLUE) { self.b.add_edge_perp_distance(pd); }
+            }
+        }
+    }
+
+    /// Returns the r, g, b perpendicular distance,
+    /// as well as the true distance in the alpha channel,
+    /// not normalized.
+    fn distance(&self, point: Vec2) -> MultiDistance {
+        let mut a = self.r.true_distance();
+        let a1 = self.g.true_distance();
+        let a2 = self.b.true_distance();
+        if a1 < a { a = a1 }
+        if a2 < a { a = a2 }
+
+        MultiDistance {
+            r: self.r.distance(point),
+            g: self.g.distance(point),
+            b: self.b.distance(point),
+            a: a.dist
+        }
+    }
+
+    fn merge(&mut self, other: &Self) {
+        self.r.merge(&other.r);
+        self.g.merge(&other.g);
+        self.b.merge(&other.b);
+    }
+
+    fn resolve(distance: &MultiDistance) -> Float {
+        distance.resolve()
+    }
+}
+
+/// Same as [`MTEdgeSelector`], without the true distance in an alpha channel.
+/// The true distance of every edge is still computed, it's what selects the nearest edge of each channel.
+#[derive(Clone)]
+struct MEdgeSelector(MTEdgeSelector);
+
+impl EdgeSelector for MEdgeSelector {
+    type Distance = [Float; 3];
+
+    fn new() -> Self {
+        Self(MTEdgeSelector::new())
+    }
+
+    fn add_edge(&mut self, point: Vec2, prev_edge: &Edge, edge: &Edge, next_edge: &Edge, cubic_distance: CubicDistance) {
+        self.0.add_edge(point, prev_edge, edge, next_edge, cubic_distance);
+    }
+
+    /// Returns the r, g, b perpendicular distance, not normalized.
+    fn distance(&self, point: Vec2) -> [Float; 3] {
+        [self.0.r.distance(point), self.0.g.distance(point), self.0.b.distance(point)]
+    }
+
+    fn merge(&mut self, other: &Self) {
+        self.0.merge(&other.0);
+    }
+
+    fn resolve(distance: &[Float; 3]) -> Float {
+        let [r, g, b] = *distance;
+        r.min(g).max(r.max(g).min(b))
+    }
+}
+
+/// The band of distances around the edges of a shape that is mapped to the pixel values 0.0 to 1.0,
+/// with 0.5 on the edges. Distances outside of the band give values past 0.0 and 1.0.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DistanceRange {
+    /// Width of the band in pixels of the generated image, like msdfgen's `-pxrange`.
+    Px(Float),
+    /// Width of the band in ems, for the same precision at every font size.
+    Em(Float)
+}
+
+impl DistanceRange {
+    /// Returns the width of the band in shape units.
+    pub fn to_units(self, units_per_em: Float, font_size_px: Float) -> Float {
+        match self {
+            DistanceRange::Px(px) => px*units_per_em/font_size_px,
+            DistanceRange::Em(em) => em*units_per_em
+        }
+    }
+
+    /// Returns the width of the band in pixels of the generated image.
+    pub fn to_pixels(self, font_size_px: Float) -> Float {
+        match self {
+            DistanceRange::Px(px) => px,
+            DistanceRange::Em(em) => em*font_size_px
+        }
+    }
+}
+
+/// Options shared by every distance field generation function.
+#[derive(Debug, Clone, Copy)]
+pub struct GeneratorConfig {
+    /// Resolve the distance contour by contour, using their winding,
+    /// so that shapes with overlapping contours don't produce seams where the contours intersect.
+    /// Default value is `true`.
+    pub overlap_support: bool,
+    /// How the distance to cubic curves is computed.
+    /// [`CubicDistance::Exact`] avoids dents in fonts made of cubic curves (CFF outlines), but is slower.
+    /// Default value is [`CubicDistance::Fast`].
+    pub cubic_distance: CubicDistance,
+    /// Skip the edges that can't be the nearest to a pixel, using a grid built for every generated glyph.
+    /// The result is the same as going through every edge, which is what disabling it does.
+    /// Default value is `true`.
+    pub acceleration: bool,
+    /// Number of threads the rows of a glyph are split between, `0` to use every available core.
+    /// The pixels are the same, and given to the callback in the same order, whatever the number of threads.
+    /// Default value is `1`.
+    pub threads: usize,
+    /// The distances that can be told apart in the generated pixels.
+    /// A narrow range keeps more precision near the edges, which matters when the pixels are quantized.
+    /// Default value is `DistanceRange::Em(2.0)`, one em on each side of the edges.
+    pub range: DistanceRange
+}
+
+impl Default for GeneratorConfig {
+    fn default() -> Self {
+        Self {
+            overlap_support: true,
+            cubic_distance: CubicDistance::Fast,
+            acceleration: true,
+            threads: 1,
+            range: DistanceRange::Em(2.0)
+        }
+    }
+}
+
+/// Adds the edges of the contour with the given indices to the selector, along with their neighbours.
+fn add_contour_edges<S: EdgeSelector, I: Iterator<Item = usize>>(selector: &mut S, contour: &Contour, edges: I, p: Vec2, cubic_distance: CubicDistance) {
+    let len = contour.edges.len();
+    for i in edges {
+        let (prev_edge, next_edge) = (&contour.edges[(i + len - 1) % len], &contour.edges[(i + 1) % len]);
+        selector.add_edge(p, prev_edge, &contour.edges[i], next_edge, cubic_distance);
+    }
+}
+
+/// Combines the distances to the contours of a shape.
+struct ContourCombiner<'a> {
+    contours: &'a [Contour],
+    /// Winding of every contour, only computed when overlapping contours are supported.
+    windings: Option<Vec<i32>>,
+    cubic_distance: CubicDistance,
+    /// Edges to look at around each point, every edge is used without it.
+    grid: Option<EdgeGrid>
+}
+
+impl<'a> ContourCombiner<'a> {
+    fn new(contours: &'a [Contour], config: &GeneratorConfig) -> Self {
+        let windings = config.overlap_support.then(|| contours.iter().map(Contour::winding).collect());
+        Self { contours, windings, cubic_distance: config.cubic_distance, grid: None }
+    }
+
+    /// Creates a combiner for the pixels of a placed shape, see [`for_each_pixel`].
+    fn for_pixels(contours: &'a [Contour], placement: &Placement, config: &GeneratorConfig) -> Self {
+        let mut combiner = Self::new(contours, config);
+        if config.acceleration {
+            // cells of about 8x8 pixels
+            let (width, height) = (placement.width, placement.height);
+            let mut region = Bounds::EMPTY;
+            region.include(placement.pixel_center(0, 0));
+            region.include(placement.pixel_center(width.saturating_sub(1), height.saturating_sub(1)));
+            combiner.grid = Some(EdgeGrid::new(contours, region, width.div_ceil(8) as usize, height.div_ceil(8) as usize));
+        }
+        combiner
+    }
+
+    fn add_contour<S: EdgeSelector>(&self, selector: &mut S, index: usize, p: Vec2) {
+        let contour = &self.contours[index];
+        match &self.grid {
+            Some(grid) => add_contour_edges(selector, contour, grid.edges(p, index).iter().copied(), p, self.cubic_distance),
+            None => add_contour_edges(selector, contour, visit_order(contour.edges.len()), p, self.cubic_distance)
+        }
+    }
+
+    fn distance<S: EdgeSelector>(&self, p: Vec2) -> S::Distance {
+        match &self.windings {
+            Some(windings) => self.overlapping_distance::<S>(windings, p),
+            None => self.simple_distance::<S>(p)
+        }
+    }
+
+    /// Takes the distance to the nearest edge, across all contours.
+    fn simple_distance<S: EdgeSelector>(&self, p: Vec2) -> S::Distance {
+        let mut selector = S::new();
+
+        for (i, c) in self.contours.iter().enumerate() {
+            if c.edges.is_empty() { continue }
+            self.add_contour(&mut selector, i, p);
+        }
+
+        selector.distance(p)
+    }
+
+    /// Computes the distance to every contour separately, and keeps the one that lies
+    /// on the boundary of the union of the contours.
+    fn overlapping_distance<S: EdgeSelector>(&self, windings: &[i32], p: Vec2) -> S::Distance {
+        let selectors: Vec<S> = self.contours.iter().enumerate().map(|(i, c)| {
+            let mut selector = S::new();
+            if !c.edges.is_empty() {
+                self.add_contour(&mut selector, i, p);
+            }
+            selector
+        }).collect();
+
+        let mut shape_selector = S::new();
+        let mut inner_selector = S::new();
+        let mut outer_selector = S::new();
+
+        for (selector, &winding) in selectors.iter().zip(windings) {
+            let edge_distance = S::resolve(&selector.distance(p));
+            shape_selector.merge(selector);
+            if winding > 0 && edge_distance >= 0.0 {
+                inner_selector.merge(selector);
+            }
+            if winding < 0 && edge_distance <= 0.0 {
+                outer_selector.merge(selector);
+            }
+        }
+
+        let shape_distance = shape_selector.distance(p);
+        let inner_distance = inner_selector.distance(p);
+        let outer_distance = outer_selector.distance(p);
+        let inner_scalar = S::resolve(&inner_distance);
+        let outer_scalar = S::resolve(&outer_distance);
+
+        let (mut distance, winding) = if inner_scalar >= 0.0 && inner_scalar.abs() <= outer_scalar.abs() {
+            let mut distance = inner_distance;
+            for (selector, &winding) in selectors.iter().zip(windings) {
+                if winding <= 0 { continue }
+                let contour_distance = selector.distance(p);
+                let contour_scalar = S::resolve(&contour_distance);
+                if contour_scalar.abs() < outer_scalar.abs() && contour_scalar > S::resolve(&distance) {
+                    distance = contour_distance;
+                }
+            }
+            (distance, 1)
+        } else if outer_scalar <= 0.0 && outer_scalar.abs() < inner_scalar.abs() {
+            let mut distance = outer_distance;
+            for (selector, &winding) in selectors.iter().zip(windings) {
+                if winding >= 0 { continue }
+                let contour_distance = selector.distance(p);
+                let contour_scalar = S::resolve(&contour_distance);
+                if contour_scalar.abs() < inner_scalar.abs() && contour_scalar < S::resolve(&distance) {
+                    distance = contour_distance;
+                }
+            }
+            (distance, -1)
+        } else {
+            return shape_distance;
+        };
+
+        for (selector, &contour_winding) in selectors.iter().zip(windings) {
+            if contour_winding == winding { continue }
+            let contour_distance = selector.distance(p);
+            let contour_scalar = S::resolve(&contour_distance);
+            let scalar = S::resolve(&distance);
+            if contour_scalar*scalar >= 0.0 && contour_scalar.abs() < scalar.abs() {
+                distance = contour_distance;
+            }
+        }
+
+        if S::resolve(&distance) == S::resolve(&shape_distance) {
+            distance = shape_distance;
+        }
+
+        distance
+    }
+}
+
+pub fn one_shot_distance(shape: &ColouredShape, p: Vec2) -> MultiDistance {
+    ContourCombiner::new(&shape.contours, &GeneratorConfig { overlap_support: false, ..Default::default() }).distance::<MTEdgeSelector>(p)
+} 
+
+/// Calls the given function with the coordinates of every pixel of the placed shape,
+/// and the position of its center in shape units.
+fn for_each_pixel<F: FnMut((u32, u32), Vec2)>(placement: &Placement, mut f: F) {
+    for y in 0..placement.height {
+        for x in 0..placement.width {
+            f((x, y), placement.pixel_center(x, y));
+        }
+    }
+}
+
+/// Returns the number of threads to use for the given setting, where `0` means every available core.
+pub fn thread_count(threads: usize) -> usize {
+    if threads == 0 {
+        std::thread::available_parallelism().map_or(1, |n| n.get())
+    } else {
+        threads
+    }
+}
+
+/// Computes the value of every pixel of the placed shape, with the rows split between threads,
+/// then calls `f` with the coordinates and value of each pixel, in the same order as [`for_each_pixel`].
+fn generate_pixels<T, D, F>(placement: &Placement, threads: usize, value: D, mut f: F)
+    where T: Send, D: Fn(Vec2) -> T + Sync, F: FnMut((u32, u32), T)
+{
+    let threads = thread_count(threads);
+    if threads <= 1 {
+        for_each_pixel(placement, |pos, p| f(pos, value(p)));
+        return;
+    }
+
+    let (width, height) = (placement.width, placement.height);
+    let rows_per_thread = height.div_ceil(threads as u32).max(1);
+    let chunks: Vec<Vec<T>> = std::thread::scope(|scope| {
+        let value = &value;
+        let handles: Vec<_> = (0..height).step_by(rows_per_thread as usize).map(|first_row| {
+            scope.spawn(move || {
+                let rows = first_row..(first_row + rows_per_thread).min(height);
+                rows.flat_map(|y| (0..width).map(move |x| value(placement.pixel_center(x, y))))
+                    .collect()
+            })
+        }).collect();
+        handles.into_iter().map(|h| h.join().unwrap()).collect()
+    });
+
+    for (i, v) in chunks.into_iter().flatten().enumerate() {
+        let i = i as u32;
+        f((i % width, i / width), v);
+    }
+}
+
+/// Flips the pixels of a distance field whose sign doesn't match the inside of the shape, as given by scanlines.
+fn correct_sign<I>(contours: &[Contour], placement: &Placement, fill_rule: FillRule, image: &mut I)
+    where I: GenericImage, I::Pixel: Pixel<Subpixel = f32>
+{
+    let mut scanline_y = Float::NAN;
+    let mut scanline = Scanline::default();
+    for_each_pixel(placement, |(x, y), p| {
+        if p.y != scanline_y {
+            scanline_y = p.y;
+            scanline = Scanline::new(contours, p.y);
+        }
+
+        let mut pixel = image.get_pixel(x, y);
+        let d = match pixel.channels() {
+            &[r, g, b, ..] => r.min(g).max(r.max(g).min(b)),
+            channels => channels[0]
+        };
+        if d != 0.5 && (d > 0.5) != scanline.is_filled(p.x, fill_rule) {
+            pixel.apply(|v| 1.0 - v);
+            image.put_pixel(x, y, pixel);
+        }
+    });
+}
+
+impl Shape {
+    /// Returns the intersections of the shape with the horizontal line at `y`, in shape units.
+    pub fn scanline(&self, y: Float) -> Scanline {
+        Scanline::new(self.contours(), y)
+    }
+
+    /// Fixes the sign of a distance field generated from this shape, so that it matches the given fill rule.
+    /// Useful for shapes with wrongly oriented or self-intersecting contours.
+    ///
+    /// The image must have been generated with the same placement.
+    /// To correct a single glyph of an atlas, pass in a sub image, for example using [`image::imageops::crop`].
+    pub fn correct_sign<I>(&self, placement: &Placement, fill_rule: FillRule, image: &mut I)
+        where I: GenericImage, I::Pixel: Pixel<Subpixel = f32>
+    {
+        correct_sign(self.contours(), placement, fill_rule, image);
+    }
+
+    /// Places the glyph in an image, see [`ColouredShape::placement`].
+    pub fn placement(&self, units_per_em: Float, font_size_px: Float, padding: Float) -> Placement {
+        Placement::new(self.bounds(), units_per_em, font_size_px, padding)
+    }
+
+    /// Returns the glyph size, in pixels.
+    ///
+    /// See [`ColouredShape::rendered_glyph_size`].
+    pub fn rendered_glyph_size(&self, units_per_em: Float, font_size_px: Float, padding: Float) -> (u32, u32) {
+        let placement = self.placement(units_per_em, font_size_px, padding);
+        (placement.width, placement.height)
+    }
+
+    /// Generates a single-channel, true signed distance field of the glyph with the given placement.
+    /// Unlike the multi-channel variants, this does not require coloring the edges of the shape.
+    ///
+    /// Calls the passed function with the X and Y coordinates of the pixel,
+    /// and the signed distance normalized in the range 0.0 to 1.0, with 0.5 being the zero.
+    /// See [`ColouredShape::generate_mtsdf`] for more details.
+    pub fn generate_sdf<F: FnMut((u32, u32), Float)>(&self, placement: &Placement, config: &GeneratorConfig, pixel_write_fun: F) {
+        let combiner = ContourCombiner::for_pixels(self.contours(), placement, config);
+        let range = config.range.to_units(placement.units_per_em, placement.font_size_px());
+        generate_pixels(placement, config.threads, |p| {
+            let d = combiner.distance::<TrueEdgeSelector>(p);
+            d/range + 0.5
+        }, pixel_write_fun);
+    }
+
+    /// Generates a single-channel perpendicular signed distance field of the glyph with the given placement.
+    ///
+    /// Distances past the ends of the edges are measured perpendicularly to their extension,
+    /// which keeps corners sharper than a true SDF when rendered.
+    /// The parameters and pixel values are the same as [`Shape::generate_sdf`].
+    pub fn generate_psdf<F: FnMut((u32, u32), Float)>(&self, placement: &Placement, config: &GeneratorConfig, pixel_write_fun: F) {
+        let combiner = ContourCombiner::for_pixels(self.contours(), placement, config);
+        let range = config.range.to_units(placement.units_per_em, placement.font_size_px());
+        generate_pixels(placement, config.threads, |p| {
+            let d = combiner.distance::<PerpEdgeSelector>(p);
+            d/range + 0.5
+        }, pixel_write_fun);
+    }
+
+    /// Generates a single-channel PSDF of the glyph into the given image, with the top-left corner of the glyph placed at `offset`.
+    pub fn write_psdf(&self, placement: &Placement, config: &GeneratorConfig, image: &mut image::ImageBuffer<image::Luma<f32>, Vec<f32>>, offset: (u32, u32)) {
+        self.generate_psdf(placement, config, |(x, y), d| {
+            image.put_pixel(offset.0 + x, offset.1 + y, image::Luma([to_f32(d)]));
+        });
+    }
+
+    /// Generates a single-channel SDF of the glyph into the given image, with the top-left corner of the glyph placed at `offset`.
+    pub fn write_sdf(&self, placement: &Placement, config: &GeneratorConfig, image: &mut image::ImageBuffer<image::Luma<f32>, Vec<f32>>, offset: (u32, u32)) {
+        self.generate_sdf(placement, config, |(x, y), d| {
+            image.put_pixel(offset.0 + x, offset.1 + y, image::Luma([to_f32(d)]));
+        });
+    }
+}
+
+impl ColouredShape {
+    /// See [`Shape::scanline`].
+    pub fn scanline(&self, y: Float) -> Scanline {
+        Scanline::new(&self.contours, y)
+    }
+
+    /// See [`Shape::correct_sign`].
+    pub fn correct_sign<I>(&self, placement: &Placement, fill_rule: FillRule, image: &mut I)
+        where I: GenericImage, I::Pixel: Pixel<Subpixel = f32>
+    {
+        correct_sign(&self.contours, placement, fill_rule, image);
+    }
+
+    /// Places the glyph in an image, scaled to the given font size.
+    ///
+    /// `units_per_em` is the size of an em in the units of the shape, which is [`ttf_parser::Face::units_per_em`] for glyphs.
+    ///
+    /// Padding refers to additional empty space to add around the (normally tight) bounding-box.
+    /// This is useful to encode additional distance information for outlines, for exemple.
+    /// At least this much padding is added in every direction, more to put the origin of the glyph on a pixel corner.
+    pub fn placement(&self, units_per_em: Float, font_size_px: Float, padding: Float) -> Placement {
+        Placement::new(self.bounds, units_per_em, font_size_px, padding)
+    }
+
+    /// Returns the glyph size, in pixels, as given by [`ColouredShape::placement`].
+    pub fn rendered_glyph_size(&self, units_per_em: Float, font_size_px: Float, padding: Float) -> (u32, u32) {
+        let placement = self.placement(units_per_em, font_size_px, padding);
+        (placement.width, placement.height)
+    }
+
+    /// Generates an MTSDF of the glyph with the given placement, usually given by [`ColouredShape::placement`].
+    ///
+    /// Calls the passed function with:
+    /// - X and Y coordinates, passed as a `(u32, u32)` tuple,
+    ///   ranging from the top left corner at `(0, 0)`,
+    ///   and the bottom-right corner at `(placement.width-1, placement.height-1)`.
+    /// - RGBA signed distance values as a `[Float; 4]` array,
+    ///   normalized in the range 0.0 to 1.0 over [`GeneratorConfig::range`], with 0.5 being the zero.
+    ///   To get the true pixel distance, use: `range.to_pixels(placement.font_size_px())*(value-0.5)`
+    ///
+    /// Partially overlapping contours are only supported if [`GeneratorConfig::overlap_support`] is enabled.
+    pub fn generate_mtsdf<F: FnMut((u32, u32), [Float; 4])>(&self, placement: &Placement, config: &GeneratorConfig, pixel_write_fun: F) {
+        let combiner = ContourCombiner::for_pixels(&self.contours, placement, config);
+        let range = config.range.to_units(placement.units_per_em, placement.font_size_px());
+        generate_pixels(placement, config.threads, |p| {
+            let mut d = combiner.distance::<MTEdgeSelector>(p);
+            d.r = d.r/range + 0.5;
+            d.g = d.g/range + 0.5;
+            d.b = d.b/range + 0.5;
+            d.a = d.a/range + 0.5;
+
+            [d.r, d.g, d.b, d.a]
+        }, pixel_write_fun);
+    }
+
+    /// Generates an MSDF of the glyph with the given placement.
+    ///
+    /// This is the same as [`ColouredShape::generate_mtsdf`], without the true distance in the alpha channel.
+    /// The passed function is called with RGB signed distance values as a `[Float; 3]` array.
+    pub fn generate_msdf<F: FnMut((u32, u32), [Float; 3])>(&self, placement: &Placement, config: &GeneratorConfig, pixel_write_fun: F) {
+        let combiner = ContourCombiner::for_pixels(&self.contours, placement, config);
+        let range = config.range.to_units(placement.units_per_em, placement.font_size_px());
+        generate_pixels(placement, config.threads, |p| {
+            let d = combiner.distance::<MEdgeSelector>(p);
+            d.map(|d| d/range + 0.5)
+        }, pixel_write_fun);
+    }
+
+    /// Generates an MSDF of the glyph into the given image, with the top-left corner of the glyph placed at `offset`.
+    pub fn write_msdf(&self, placement: &Placement, config: &GeneratorConfig, image: &mut image::Rgb32FImage, offset: (u32, u32)) {
+        self.generate_msdf(placement, config, |(x, y), pixel| {
+            image.put_pixel(offset.0 + x, offset.1 + y, image::Rgb(pixel.map(to_f32)));
+        });
+    }
+
+    /// Generates an MTSDF of the glyph into the given image, with the top-left corner of the glyph placed at `offset`.
+    ///
+    /// See [`ColouredShape::generate_mtsdf`] for the meaning of the parameters and of the pixel values.
+    pub fn write_mtsdf(&self, placement: &Placement, config: &GeneratorConfig, image: &mut image::Rgba32FImage, offset: (u32, u32)) {
+        self.generate_mtsdf(placement, config, |(x, y), pixel| {
+            image.put_pixel(offset.0 + x, offset.1 + y, image::Rgba(pixel.map(to_f32)));
+        });
+    }
+}
@@ -0,0 +1,71 @@
+use super::{Contour, Float};
+
+/// Decides which regions of a shape are filled, from the winding number of its contours around a point.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum FillRule {
+    /// Filled when the winding number is not zero.
+    #[default]
+    NonZero,
+    /// Filled when the winding number is odd.
+    EvenOdd,
+    /// Filled when the winding number is strictly positive.
+    Positive,
+    /// Filled when the winding number is strictly negative.
+    Negative
+}
+
+impl FillRule {
+    pub fn is_filled(self, winding: i32) -> bool {
+        match self {
+            FillRule::NonZero => winding != 0,
+            FillRule::EvenOdd => winding % 2 != 0,
+            FillRule::Positive => winding > 0,
+            FillRule::Negative => winding < 0
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Intersection {
+    pub x: Float,
+    /// 1 if the shape crosses the scanline going up, -1 if it's going down
+    pub direction: i32
+}
+
+/// The intersections of a shape with a horizontal line, sorted from left to right.
+#[derive(Debug, Clone, Default)]
+pub struct Scanline {
+    intersections: Vec<Intersection>
+}
+
+impl Scanline {
+    /// Intersects the given contours with the horizontal line at `y`.
+    pub fn new(contours: &[Contour], y: Float) -> Self {
+        let mut intersections: Vec<_> = contours.iter()
+            .flat_map(|c| &c.edges)
+            .flat_map(|e| e.segment.scanline_intersections(y))
+            .map(|(x, direction)| Intersection { x, direction })
+            .collect();
+
+        intersections.sort_by(|a, b| a.x.total_cmp(&b.x));
+        Scanline { intersections }
+    }
+
+    pub fn intersections(&self) -> &[Intersection] {
+        &self.intersections
+    }
+
+    /// Returns the winding number of the shape around the point at `x` on the scanline.
+    /// Contours oriented clockwise have a positive winding.
+    pub fn winding(&self, x: Float) -> i32 {
+        self.intersections.iter()
+            .take_while(|i| i.x < x)
+            .map(|i| i.direction)
+            .sum()
+    }
+
+    /// Returns whether the point at `x` on the scanline is inside of the shape.
+    pub fn is_filled(&self, x: Float, fill_rule: FillRule) -> bool {
+        fill_rule.is_filled(self.winding(x))
+    }
+}
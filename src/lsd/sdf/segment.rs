@@ -0,0 +1,547 @@
+use smallvec::SmallVec;
+
+use super::{lerp, Bounds, Color, Edge, Float, SignedDistance, Vec2};
+
+/// How [`Segment::signed_distance`] looks for the nearest point of a cubic curve.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum CubicDistance {
+    /// A few Newton iterations from evenly spaced starting points.
+    /// Fast, but can miss the nearest point of tight or looping curves.
+    #[default]
+    Fast,
+    /// Isolates every extremum of the distance by subdividing the curve,
+    /// then refines each of them until its parameter is known within `tolerance`.
+    Exact { tolerance: Float }
+}
+
+const BINOMIAL_2: [Float; 3] = [1.0, 2.0, 1.0];
+const BINOMIAL_3: [Float; 4] = [1.0, 3.0, 3.0, 1.0];
+const BINOMIAL_5: [Float; 6] = [1.0, 5.0, 10.0, 10.0, 5.0, 1.0];
+
+/// Splits a polynomial in Bernstein form at `t`, returning the coefficients of both halves.
+fn split_bernstein(c: [Float; 6], t: Float) -> ([Float; 6], [Float; 6]) {
+    let (mut left, mut right) = ([0.0; 6], [0.0; 6]);
+    let mut c = c;
+    for i in 0..6 {
+        left[i] = c[0];
+        right[5 - i] = c[5 - i];
+        for j in 0..5 - i {
+            c[j] = lerp(c[j], c[j + 1], t);
+        }
+    }
+    (left, right)
+}
+
+fn eval_bernstein(c: [Float; 6], t: Float) -> Float {
+    split_bernstein(c, t).1[0]
+}
+
+/// Pushes the roots of the polynomial `c`, in Bernstein form over `[t0, t1]`, to `roots`.
+///
+/// The number of sign changes of the coefficients bounds the number of roots, so intervals without
+/// any are discarded, and intervals with a single one contain exactly one root, found by bisection.
+/// Other intervals are subdivided, until they are smaller than `tolerance`.
+fn isolate_roots(c: [Float; 6], t0: Float, t1: Float, tolerance: Float, roots: &mut SmallVec<[Float; 5]>) {
+    let sign_changes = c.windows(2).filter(|w| (w[0] < 0.0) != (w[1] < 0.0)).count();
+    if sign_changes == 0 { return }
+
+    if t1 - t0 <= tolerance {
+        roots.push(0.5*(t0 + t1));
+    } else if sign_changes == 1 {
+        let (mut a, mut b) = (0.0, 1.0);
+        while (b - a)*(t1 - t0) > tolerance {
+            let m = 0.5*(a + b);
+            if (eval_bernstein(c, m) < 0.0) == (c[0] < 0.0) { a = m } else { b = m }
+        }
+        roots.push(lerp(t0, t1, 0.5*(a + b)));
+    } else {
+        let (left, right) = split_bernstein(c, 0.5);
+        let tm = 0.5*(t0 + t1);
+        isolate_roots(left, t0, tm, tolerance, roots);
+        isolate_roots(right, tm, t1, tolerance, roots);
+    }
+}
+
+/// Returns the parameters strictly between 0 and 1 where the distance from `p` to the cubic curve
+/// with the given control points reaches an extremum, i.e. the roots of the quintic `(B(t) - p)·B'(t)`.
+fn cubic_distance_extrema(points: [Vec2; 4], p: Vec2, tolerance: Float) -> SmallVec<[Float; 5]> {
+    let q = points.map(|c| c - p);
+    let d = [0, 1, 2].map(|j| 3.0*(points[j + 1] - points[j]));
+
+    // product of the two polynomials, in Bernstein form
+    let mut c = [0.0; 6];
+    for i in 0..4 {
+        for j in 0..3 {
+            c[i + j] += BINOMIAL_3[i]*BINOMIAL_2[j]*q[i].dot(d[j]);
+        }
+    }
+    for k in 0..6 {
+        c[k] /= BINOMIAL_5[k];
+    }
+
+    let mut roots = SmallVec::new();
+    isolate_roots(c, 0.0, 1.0, tolerance.max(Float::EPSILON), &mut roots);
+    roots.retain(|t| *t > 0.0 && *t < 1.0);
+    roots
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Segment {
+    Line(Vec2, Vec2),
+    Quad(Vec2, Vec2, Vec2),
+    Cubic(Vec2, Vec2, Vec2, Vec2)
+}
+
+impl Segment {
+    pub fn white_edge(self) -> Edge {
+        self.colored(Color::WHITE)
+    }
+
+    pub fn colored(self, color: Color) -> Edge {
+        Edge { segment: self, color }
+    }
+
+    /// Returns the end and control points of the segment, in order.
+    pub fn points(&self) -> SmallVec<[Vec2; 4]> {
+        match *self {
+            Segment::Line(a, b) => SmallVec::from_slice(&[a, b]),
+            Segment::Quad(a, b, c) => SmallVec::from_slice(&[a, b, c]),
+            Segment::Cubic(a, b, c, d) => SmallVec::from_slice(&[a, b, c, d])
+        }
+    }
+
+    pub fn start(&self) -> Vec2 {
+        match *self {
+            Segment::Line(a, _) | Segment::Quad(a, _, _) | Segment::Cubic(a, _, _, _) => a
+        }
+    }
+
+    pub fn end(&self) -> Vec2 {
+        match *self {
+            Segment::Line(_, b) | Segment::Quad(_, _, b) | Segment::Cubic(_, _, _, b) => b
+        }
+    }
+
+    /// Returns the same segment, with its end point moved to `p`.
+    pub fn with_end(self, p: Vec2) -> Self {
+        match self {
+            Segment::Line(a, _) => Segment::Line(a, p),
+            Segment::Quad(a, b, _) => Segment::Quad(a, b, p),
+            Segment::Cubic(a, b, c, _) => Segment::Cubic(a, b, c, p)
+        }
+    }
+
+    /// Returns the same segment, going in the opposite direction.
+    pub fn reverse(self) -> Self {
+        match self {
+            Segment::Line(a, b) => Segment::Line(b, a),
+            Segment::Quad(a, b, c) => Segment::Quad(c, b, a),
+            Segment::Cubic(a, b, c, d) => Segment::Cubic(d, c, b, a)
+        }
+    }
+
+    /// Sample the segment at the given percentage
+    pub fn sample(&self, t: Float) -> Vec2 {
+        match *self {
+            Segment::Line(a, b) => lerp(a, b, t),
+            Segment::Quad(a, b, c) => lerp(
+                lerp(a, b, t),
+                lerp(b, c, t),
+                t
+            ),
+            Segment::Cubic(a, b, c, d) => {
+                let p12 = lerp(b, c, t);
+                lerp(lerp(lerp(a, b, t), p12, t), lerp(p12, lerp(c, d, t), t), t)
+            }
+        }
+    }
+
+    /// Returns the direction the edge has at the point specified by the parameter.
+    pub fn direction(&self, t: Float) -> Vec2 {
+        match *self {
+            Segment::Line(a, b) => b - a,
+            Segment::Quad(a, b, c) => {
+                let tangent = lerp(b - a, c - b, t);
+                if tangent.x == 0.0 && tangent.y == 0.0 { c - a }
+                else { tangent }
+            },
+            Segment::Cubic(a, b, c, d) => {
+                let tangent = lerp(
+                    lerp(b - a, c - b, t),
+                    lerp(c - b, d - c, t),
+                    t
+                );
+
+                if tangent.x == 0.0 && tangent.y == 0.0 {
+                    if t == 0.0 { c - a }
+                    else if t == 1.0 { d - b }
+                    else { tangent }
+                } else { tangent }
+            },
+        }
+    }
+
+    /// Returns the parameters, sorted and strictly between 0 and 1, at which the given coordinate of the segment changes direction.
+    fn extrema(&self, coordinate: fn(Vec2) -> Float) -> SmallVec<[Float; 2]> {
+        let roots = match *self {
+            Segment::Line(..) => return SmallVec::new(),
+            Segment::Quad(a, b, c) => {
+                let (a, b, c) = (coordinate(a), coordinate(b), coordinate(c));
+                roots::find_roots_linear(a - 2.0*b + c, b - a)
+            }
+            Segment::Cubic(a, b, c, d) => {
+                let (a, b, c, d) = (coordinate(a), coordinate(b), coordinate(c), coordinate(d));
+                roots::find_roots_quadratic(-a + 3.0*b - 3.0*c + d, 2.0*(a - 2.0*b + c), b - a)
+            }
+        };
+
+        roots.as_ref().iter().copied().filter(|&t| t > 0.0 && t < 1.0).collect()
+    }
+
+    /// Returns the parameters, sorted and strictly between 0 and 1, at which the segment changes vertical direction.
+    pub fn y_extrema(&self) -> SmallVec<[Float; 2]> {
+        self.extrema(|p| p.y)
+    }
+
+    /// Returns the exact bounding box of the segment, which can be smaller than the one of its control points.
+    pub fn bounds(&self) -> Bounds {
+        let mut bounds = Bounds::EMPTY;
+        bounds.include(self.start());
+        bounds.include(self.end());
+        for t in self.extrema(|p| p.x).into_iter().chain(self.y_extrema()) {
+            bounds.include(self.sample(t));
+        }
+        bounds
+    }
+
+    /// Returns the points where the segment crosses the horizontal line at `y`,
+    /// as the X coordinate of the crossing and the vertical direction of the segment there (1 going up, -1 going down).
+    ///
+    /// An endpoint lying exactly on the line counts as being below it,
+    /// so a crossing at the junction of two segments is only reported once.
+    pub fn scanline_intersections(&self, y: Float) -> SmallVec<[(Float, i32); 3]> {
+        let mut intersections = SmallVec::new();
+
+        if let &Segment::Line(a, b) = self {
+            if (a.y <= y) != (b.y <= y) {
+                let x = a.x + (y - a.y)*(b.x - a.x)/(b.y - a.y);
+                intersections.push((x, if b.y > a.y { 1 } else { -1 }));
+            }
+            return intersections;
+        }
+
+        // split the curve into vertically monotonic pieces, which can be crossed at most once
+        let extrema = self.y_extrema();
+        let bounds = std::iter::once(0.0).chain(extrema).chain(std::iter::once(1.0));
+        let mut prev: Option<Float> = None;
+        for t1 in bounds {
+            let Some(t0) = prev.replace(t1) else { continue };
+
+            let (y0, y1) = (self.sample(t0).y, self.sample(t1).y);
+            if (y0 <= y) == (y1 <= y) { continue }
+
+            let (mut lo, mut hi) = (t0, t1);
+            for _ in 0..32 {
+                let mid = 0.5*(lo + hi);
+                if (self.sample(mid).y <= y) == (y0 <= y) { lo = mid } else { hi = mid }
+            }
+
+            intersections.push((self.sample(0.5*(lo + hi)).x, if y1 > y0 { 1 } else { -1 }));
+        }
+
+        intersections
+    }
+
+    /// Split this segment into three equal parts
+    pub fn split_in_three(self) -> [Self; 3] {
+        match self {
+            Segment::Line(a, b) => {
+                let third = self.sample(1.0/3.0);
+                let two = self.sample(2.0/3.0);
+                [Segment::Line(a, third), Segment::Line(third, two), Segment::Line(two, b)]
+            }
+            Segment::Quad(a, b, c) => {
+                let third = self.sample(1.0/3.0);
+                let two = self.sample(2.0/3.0);
+                [
+                    Segment::Quad(a, lerp(a, b, 1.0/3.0), third),
+                    Segment::Quad(third, lerp(lerp(a, b, 5.0/9.0), lerp(b, c, 4.0/9.0), 0.5), two),
+                    Segment::Quad(two, lerp(b, c, 2.0/3.0), c)
+                ]
+            }
+            Segment::Cubic(a, b, c, d) => {
+                let third = self.sample(1.0/3.0);
+                let two = self.sample(2.0/3.0);
+
+                let first = Segment::Cubic(
+                    a,
+                    if a == b { a } else { lerp(a, b, 1.0/3.0) },
+                    lerp(lerp(a, b, 1.0/3.0), lerp(b, c, 1.0/3.0), 1.0/3.0),
+                    third
+                );
+
+                let second = Segment::Cubic(
+                    third,
+                    lerp(
+                        lerp(lerp(a, b, 1.0/3.0), lerp(b, c, 1.0/3.0), 1.0/3.0),
+                        lerp(lerp(b, c, 1.0/3.0), lerp(c, d, 1.0/3.0), 1.0/3.0),
+                        2.0/3.0
+                    ),
+                    lerp(
+                        lerp(lerp(a, b, 2.0/3.0), lerp(b, c, 2.0/3.0), 2.0/3.0),
+                        lerp(lerp(b, c, 2.0/3.0), lerp(c, d, 2.0/3.0), 2.0/3.0),
+                        1.0/3.0
+                    ),
+                    two
+                );
+
+                let third = Segment::Cubic(
+                    two,
+                    lerp(lerp(b, c, 2.0/3.0), lerp(c, d, 2.0/3.0), 2.0/3.0),
+                    if c == d { d } else { lerp(c, d, 2.0/3.0) },
+                    d
+                );
+
+                [first, second, third]
+            }
+        }
+    }
+
+    /// Returns the closest signed distance and the t value corresponding
+    /// to the closest point in the curve.
+    /// `cubic_distance` chooses how the closest point is found on cubic curves.
+    pub fn signed_distance(&self, p: Vec2, cubic_distance: CubicDistance) -> (SignedDistance, Float) {
+        match *self {
+            Segment::Line(p0, p1) => {
+                let aq = p - p0;
+                let ab = p1 - p0;
+                let t = aq.dot(ab) / ab.length_sqr();
+                let eq = if t <= 0.5 { p0 } else { p1 } - p;
+                let endpoint_dist = eq.length();
+                if t > 0.0 && t < 1.0 {
+                    let ortho_dist = ab.orthogonal(false).normalize().dot(aq);
+                    if ortho_dist.abs() < endpoint_dist {
+                        return (SignedDistance { dist: ortho_dist, dot: 0.0 }, t);
+                    }
+                }
+
+                (SignedDistance { dist: aq.cross(ab).signum()*endpoint_dist, dot: ab.normalize().dot(eq.normalize()).abs() }, t)
+            }
+            Segment::Quad(p0, p1, p2) => {
+                let qa = p0 - p;
+                let ab = p1 - p0;
+                let br = p2 - p1 - ab;
+                let a = br.length_sqr();
+                let b = 3.0 * ab.dot(br);
+                let c = 2.0 * ab.length_sqr() + qa.dot(br);
+                let d = qa.dot(ab);
+
+                let roots = roots::find_roots_cubic(a, b, c, d);
+                let (num_solutions, solutions) = match roots {
+                    roots::Roots::No(_) => (0, [0.0, 0.0, 0.0]),
+                    roots::Roots::One([x0]) => (1, [x0, 0.0, 0.0]),
+                    roots::Roots::Two([x0, x1]) => (2, [x0, x1, 0.0]),
+                    roots::Roots::Three([x0, x1, x2]) => (3, [x0, x1, x2]),
+                    _ => unreachable!()
+                };
+
+                let ep_dir = self.direction(0.0);
+                let mut min_dist = ep_dir.cross(qa).signum() * qa.length();
+                let mut t = -qa.dot(ep_dir) / ep_dir.length_sqr();
+
+                let ep_dir = self.direction(1.0);
+                { 
+                    let distance = (p2 - p).length();
+                    if distance < min_dist.abs() {
+                        min_dist = ep_dir.cross(p2 - p).signum() * distance;
+                        t = (p - p1).dot(ep_dir) / ep_dir.length_sqr();
+                    }
+                }
+
+                for &solution in solutions.iter().take(num_solutions) {
+                    if solution > 0.0 && solution < 1.0 {
+                        let qe = qa + 2.0*solution*ab + solution*solution*br;
+                        let distance = qe.length();
+                        if distance <= min_dist.abs() {
+                            min_dist = (ab + solution*br).cross(qe).signum() * distance;
+                            t = solution;
+                        }
+                    }
+                }
+
+                let dist = min_dist;
+                if (0.0..=1.0).contains(&t) {
+                    (SignedDistance { dist, dot: 0.0 }, t)
+                } else if t < 0.0 {
+                    (SignedDistance { dist, dot: self.direction(0.0).normalize().dot(qa.normalize()).abs() }, t)
+                } else {
+                    (SignedDistance { dist, dot: self.direction(1.0).normalize().dot((p2 - p).normalize()).abs() }, t)
+                }
+            }
+            Segment::Cubic(p0, p1, p2, p3) => {
+                let qa = p0 - p;
+                let ab = p1 - p0;
+                let br = p2 - p1 - ab;
+                let r#as = (p3 - p2) - (p2 - p1) - br;
+
+                let ep_dir = self.direction(0.0);
+                let mut min_distance = ep_dir.cross(qa).signum() * qa.length();
+                let mut t = -qa.dot(ep_dir)/ep_dir.length_sqr();
+
+                let ep_dir = self.direction(1.0);
+                {
+                    let distance = (p3 - p).length();
+                    if distance < min_distance.abs() {
+                        min_distance = ep_dir.cross(p3 - p).signum() * distance;
+                        t = (ep_dir - (p3 - p)).dot(ep_dir) / ep_dir.length_sqr();
+                    }
+                }
+
+                let mut param = t;
+
+                match cubic_distance {
+                    CubicDistance::Fast => {
+                        // Iterative minimum distance search
+                        const CUBIC_SEARCH_STARTS: usize = 4;
+                        const CUBIC_SEARCH_STEPS: usize = 4;
+                        for i in 0..=CUBIC_SEARCH_STARTS {
+                            let mut t = (i as Float) / CUBIC_SEARCH_STARTS as Float;
+                            let mut qe = qa + 3.0*t*ab + 3.0*t*t*br + t*t*t*r#as;
+                            for _ in 0..CUBIC_SEARCH_STEPS {
+                                //  import t
+                                let d1 = 3.0*ab + 6.0*t*br + 3.0*t*t*r#as;
+                                let d2 = 6.0*br + 6.0*t*r#as;
+
+                                t -= qe.dot(d1)/(d1.length_sqr() + qe.dot(d2));
+                                if t <= 0.0 || t >= 1.0 { break }
+
+                                qe = qa + 3.0*t*ab + 3.0*t*t*br + t*t*t*r#as;
+                                let distance = qe.length();
+                                if distance < min_distance.abs() {
+                                    min_distance = d1.cross(qe).signum() * distance;
+                                    param = t;
+                                }
+                            }
+                        }
+                    }
+                    CubicDistance::Exact { tolerance } => {
+                        for t in cubic_distance_extrema([p0, p1, p2, p3], p, tolerance) {
+                            let qe = qa + 3.0*t*ab + 3.0*t*t*br + t*t*t*r#as;
+                            let distance = qe.length();
+                            if distance < min_distance.abs() {
+                                let d1 = 3.0*ab + 6.0*t*br + 3.0*t*t*r#as;
+                                min_distance = d1.cross(qe).signum() * distance;
+                                param = t;
+                            }
+                        }
+                    }
+                }
+
+                let dist = min_distance;
+                if (0.0..=1.0).contains(&param) {
+                    (SignedDistance { dist, dot: 0.0 }, param)
+                } else if param < 0.0 {
+                    (SignedDistance { dist, dot: self.direction(0.0).normalize().dot(qa.normalize()).abs() }, param)
+                } else {
+                    (SignedDistance { dist, dot: self.direction(1.0).normalize().dot((p3 - p).normalize()).abs() }, param)
+                }
+            }
+        }
+    }
+
+    pub fn distance_to_perp_dist(&self, dist: SignedDistance, p: Vec2, t: Float) -> SignedDistance {
+        if t < 0.0 {
+            let dir = self.direction(0.0).normalize();
+            let aq = p - self.sample(0.0);
+            let ts = aq.dot(dir);
+            if ts < 0.0 {
+                let perp_dist = aq.cross(dir);
+                if perp_dist.abs() <= dist.dist.abs() {
+                    return SignedDistance {
+                        dist: perp_dist, dot: 0.0
+                    };
+                }
+            }
+        } else if t > 1.0 {
+            let dir = self.direction(1.0).normalize();
+            let bq = p - self.sample(1.0);
+            let ts = bq.dot(dir);
+            if ts > 0.0 {
+                let perp_dist = bq.cross(dir);
+                if perp_dist.abs() <= dist.dist.abs() {
+                    return SignedDistance {
+                        dist: perp_dist, dot: 0.0
+                    }
+                }
+            }
+        }
+
+        dist
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sdf::vec2;
+
+    fn brute_force_distance(segment: &Segment, p: Vec2) -> Float {
+        const SAMPLES: usize = 20_000;
+        (0..=SAMPLES)
+            .map(|i| (segment.sample(i as Float/SAMPLES as Float) - p).length())
+            .fold(Float::MAX, Float::min)
+    }
+
+    #[test]
+    fn exact_cubic_distance_matches_sampling() {
+        let cubics = [
+            // s-curve
+            [vec2(0.0, 0.0), vec2(40.0, 100.0), vec2(60.0, -100.0), vec2(100.0, 0.0)],
+            // cusp at t = 0.5
+            [vec2(0.0, 0.0), vec2(100.0, 100.0), vec2(0.0, 100.0), vec2(100.0, 0.0)],
+            // loop
+            [vec2(0.0, 0.0), vec2(150.0, 100.0), vec2(-50.0, 100.0), vec2(100.0, 0.0)],
+            // almost straight
+            [vec2(0.0, 0.0), vec2(33.0, 0.001), vec2(66.0, -0.001), vec2(100.0, 0.0)],
+            // control points on the endpoints
+            [vec2(0.0, 0.0), vec2(0.0, 0.0), vec2(100.0, 50.0), vec2(100.0, 50.0)],
+            // folded back on itself
+            [vec2(0.0, 0.0), vec2(100.0, 0.0), vec2(-50.0, 0.0), vec2(50.0, 0.1)]
+        ];
+
+        for [a, b, c, d] in cubics {
+            let segment = Segment::Cubic(a, b, c, d);
+            for i in 0..=12 {
+                for j in 0..=12 {
+                    let p = vec2(-30.0 + 13.0*i as Float, -80.0 + 15.0*j as Float);
+                    let (exact, t) = segment.signed_distance(p, CubicDistance::Exact { tolerance: 1e-6 });
+                    let (fast, _) = segment.signed_distance(p, CubicDistance::Fast);
+                    let expected = brute_force_distance(&segment, p);
+
+                    // sampling overestimates the distance by at most the distance between samples
+                    let epsilon = 0.02;
+                    assert!((exact.dist.abs() - expected).abs() < epsilon, "{segment:?} at {p:?}: {} instead of {expected}", exact.dist);
+                    assert!(exact.dist.abs() <= fast.dist.abs() + epsilon, "{segment:?} at {p:?}: exact {} further than fast {}", exact.dist, fast.dist);
+                    if (0.0..=1.0).contains(&t) {
+                        assert!(((segment.sample(t) - p).length() - exact.dist.abs()).abs() < epsilon);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn distance_extrema_are_isolated() {
+        // the curve is symmetric around the vertical line going through p, so its middle is an extremum
+        let segment = [vec2(-50.0, 0.0), vec2(-50.0, 60.0), vec2(50.0, 60.0), vec2(50.0, 0.0)];
+        let roots = cubic_distance_extrema(segment, vec2(0.0, -10.0), 1e-6);
+        assert!(roots.iter().any(|t| (t - 0.5).abs() < 1e-4), "{roots:?}");
+
+        // far away from a straight curve, there is a single minimum
+        let segment = [vec2(0.0, 0.0), vec2(10.0, 0.0), vec2(20.0, 0.0), vec2(30.0, 0.0)];
+        let roots = cubic_distance_extrema(segment, vec2(12.0, 50.0), 1e-6);
+        assert_eq!(roots.len(), 1);
+        assert!((roots[0] - 0.4).abs() < 1e-4, "{roots:?}");
+    }
+}
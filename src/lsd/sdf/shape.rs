@@ -0,0 +1,469 @@
+use super::{scanline::{FillRule, Scanline}, segment::CubicDistance, Bounds, Color, Contour, Float, Segment, Vec2};
+
+fn extract_seed_bit(seed: &mut u64) -> u64 {
+    let v = *seed & 1;
+    *seed >>= 1;
+    v
+}
+
+fn extract_seed_mod3(seed: &mut u64) -> u64 {
+    let v = *seed % 3;
+    *seed /= 3;
+    v
+}
+
+fn init_color(seed: &mut u64) -> Color {
+    const COLORS: [Color; 3] = [Color::CYAN, Color::MAGENTA, Color::YELLOW];
+    COLORS[extract_seed_mod3(seed) as usize]
+}
+
+fn switch_color(color: &mut Color, seed: &mut u64) {
+    let bit = extract_seed_bit(seed);
+    let shifted = color.bits() << (1+bit);
+    *color = Color::from_bits((shifted|shifted>>3) & Color::WHITE.bits()).unwrap();
+}
+
+fn switch_color_constrained(color: &mut Color, seed: &mut u64, banned: Color) {
+    let combined = *color & banned;
+    if combined == Color::RED || combined == Color::GREEN || combined == Color::BLUE {
+        *color = combined ^ Color::WHITE;
+    } else {
+        switch_color(color, seed);
+    }
+}
+
+
+/// For each position < n, this function will return -1, 0, or 1,
+/// depending on whether the position is closer to the beginning, middle, or end, respectively.
+/// It is guaranteed that the output will be balanced in that the total for positions 0 through n-1 will be zero.
+fn symmetrical_trichotomy(position: i32, n: i32) -> i32 {
+    (3.0 + 2.875*(position as Float)/(n as Float - 1.0)-1.4375 + 0.5) as i32 - 3
+}
+
+/// How [`Shape::color_edges`] assigns colors to the edges between corners.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum EdgeColoring {
+    /// Switches color at every corner.
+    #[default]
+    Simple,
+    /// Like [`EdgeColoring::Simple`], except that short edges between two longer ones, like ink traps,
+    /// don't take part in the color switching, which avoids artifacts at small sizes.
+    InkTrap,
+    /// Colors the edges between corners of every contour together,
+    /// so that edges close to each other get different colors, even across contours.
+    /// Slower, but avoids artifacts between close strokes of complex shapes.
+    Distance
+}
+
+/// Estimates the length of a segment from a few points along it.
+fn estimate_length(segment: &Segment) -> Float {
+    const PRECISION: usize = 4;
+
+    let mut prev = segment.sample(0.0);
+    (1..=PRECISION).map(|i| {
+        let cur = segment.sample(i as Float/PRECISION as Float);
+        let length = (cur - prev).length();
+        prev = cur;
+        length
+    }).sum()
+}
+
+/// Colors a contour with multiple corners for [`EdgeColoring::InkTrap`].
+///
+/// A corner starting a spline shorter than both of its neighbours is minor:
+/// the main corners are colored as if it wasn't there,
+/// and its spline takes the color missing from the splines around it.
+fn color_ink_trap_corners(contour: &mut Contour, corners: &[usize], color: &mut Color, seed: &mut u64) {
+    let count = corners.len();
+    let m = contour.edges.len();
+
+    // estimated length of the spline ending at each corner
+    let lengths: Vec<Float> = (0..count).map(|i| {
+        let start = corners[(i + count - 1) % count];
+        let edge_count = (corners[i] + m - start) % m;
+        (0..edge_count).map(|j| estimate_length(&contour.edges[(start + j) % m].segment)).sum()
+    }).collect();
+
+    let mut minor = vec![false; count];
+    let mut major_count = count;
+    if count > 3 {
+        for i in 0..count {
+            if lengths[i] > lengths[(i + 1) % count] && lengths[(i + 1) % count] < lengths[(i + 2) % count] {
+                minor[i] = true;
+                major_count -= 1;
+            }
+        }
+    }
+
+    let mut colors = vec![Color::BLACK; count];
+    let mut initial_color = Color::BLACK;
+    for i in 0..count {
+        if minor[i] { continue }
+        major_count -= 1;
+        switch_color_constrained(color, seed, if major_count == 0 { initial_color } else { Color::BLACK });
+        colors[i] = *color;
+        if initial_color == Color::BLACK {
+            initial_color = *color;
+        }
+    }
+    for i in 0..count {
+        if minor[i] {
+            colors[i] = (*color & colors[(i + 1) % count]) ^ Color::WHITE;
+        } else {
+            *color = colors[i];
+        }
+    }
+
+    let mut spline = 0;
+    let start = corners[0];
+    *color = colors[0];
+    for i in 0..m {
+        let idx = (start + i) % m;
+        if spline + 1 < count && corners[spline + 1] == idx {
+            spline += 1;
+            *color = colors[spline];
+        }
+        contour.edges[idx].color = *color;
+    }
+}
+
+/// Pushes the index of every edge of the contour starting with a corner:
+/// where the direction of the contour changes by more than the angle whose sine is `cross_threshold`.
+fn find_corners(contour: &Contour, cross_threshold: Float, corners: &mut Vec<usize>) {
+    let mut prev = contour.edges.last().unwrap().segment.direction(1.0);
+    for (i, edge) in contour.edges.iter().enumerate() {
+        if is_corner(prev.normalize(), edge.segment.direction(0.0).normalize(), cross_threshold) {
+            corners.push(i);
+        }
+        prev = edge.segment.direction(1.0);
+    }
+}
+
+/// Estimates the distance between two segments, from a few points along each of them.
+fn estimate_distance(a: &Segment, b: &Segment) -> Float {
+    const PRECISION: usize = 4;
+
+    (0..=PRECISION).map(|i| i as Float/PRECISION as Float)
+        .flat_map(|t| [
+            b.signed_distance(a.sample(t), CubicDistance::Fast).0.dist.abs(),
+            a.signed_distance(b.sample(t), CubicDistance::Fast).0.dist.abs()
+        ])
+        .fold(Float::MAX, Float::min)
+}
+
+/// Edges of a contour that get the same color, usually the edges between two corners.
+struct Spline {
+    contour: usize,
+    edges: Vec<usize>,
+    /// The splines around this one in its contour, which need to have a different color.
+    neighbours: [usize; 2]
+}
+
+/// Colors the edges for [`EdgeColoring::Distance`].
+///
+/// Splines are colored one after the other, each with the color that isn't used by its neighbours
+/// and is the farthest away from the splines that already have it.
+fn color_by_distance(contours: &mut [Contour], cross_threshold: Float, seed: &mut u64) {
+    let mut splines: Vec<Spline> = vec![];
+    let mut corners = vec![];
+
+    for (c, contour) in contours.iter_mut().enumerate() {
+        if contour.edges.is_empty() { continue }
+        corners.clear();
+        find_corners(contour, cross_threshold, &mut corners);
+
+        let first = splines.len();
+        if corners.is_empty() { // smooth contour
+            splines.push(Spline { contour: c, edges: (0..contour.edges.len()).collect(), neighbours: [first; 2] });
+        } else if corners.len() == 1 { // teardrop shape, the edges in the middle are white
+            if contour.edges.len() < 3 {
+                // split the edges, with the corner at the start of the contour
+                let corner = corners[0];
+                contour.edges = [corner, 1 - corner][..contour.edges.len()].iter()
+                    .flat_map(|&i| contour.edges[i].segment.split_in_three())
+                    .map(Segment::white_edge)
+                    .collect();
+                corners[0] = 0;
+            }
+
+            let m = contour.edges.len();
+            let mut sides = [vec![], vec![]];
+            for i in 0..m {
+                let index = (corners[0] + i) % m;
+                match symmetrical_trichotomy(i as i32, m as i32) {
+                    -1 => sides[0].push(index),
+                    1 => sides[1].push(index),
+                    _ => contour.edges[index].color = Color::WHITE
+                }
+            }
+            let [a, b] = sides;
+            splines.push(Spline { contour: c, edges: a, neighbours: [first + 1; 2] });
+            splines.push(Spline { contour: c, edges: b, neighbours: [first; 2] });
+        } else { // edges between every two corners
+            let m = contour.edges.len();
+            let count = corners.len();
+            for i in 0..count {
+                let (start, end) = (corners[i], corners[(i + 1) % count]);
+                let edge_count = (end + m - start) % m;
+                splines.push(Spline {
+                    contour: c,
+                    edges: (0..edge_count).map(|j| (start + j) % m).collect(),
+                    neighbours: [first + (i + count - 1) % count, first + (i + 1) % count]
+                });
+            }
+        }
+    }
+
+    let n = splines.len();
+    let mut distances = vec![Float::MAX; n*n];
+    for i in 0..n {
+        for j in i + 1..n {
+            let mut distance = Float::MAX;
+            for &a in &splines[i].edges {
+                for &b in &splines[j].edges {
+                    let a = &contours[splines[i].contour].edges[a].segment;
+                    let b = &contours[splines[j].contour].edges[b].segment;
+                    distance = distance.min(estimate_distance(a, b));
+                }
+            }
+            distances[i*n + j] = distance;
+            distances[j*n + i] = distance;
+        }
+    }
+
+    const COLORS: [Color; 3] = [Color::CYAN, Color::MAGENTA, Color::YELLOW];
+    let mut colors: Vec<Option<Color>> = vec![None; n];
+    for i in 0..n {
+        let offset = extract_seed_mod3(seed) as usize;
+        let mut best = (Color::BLACK, -1.0);
+        for k in 0..3 {
+            let color = COLORS[(offset + k) % 3];
+            if splines[i].neighbours.iter().any(|&j| j != i && colors[j] == Some(color)) { continue }
+
+            let distance = (0..n)
+                .filter(|&j| colors[j] == Some(color))
+                .map(|j| distances[i*n + j])
+                .fold(Float::MAX, Float::min);
+            if distance > best.1 {
+                best = (color, distance);
+            }
+        }
+
+        colors[i] = Some(best.0);
+        for &e in &splines[i].edges {
+            contours[splines[i].contour].edges[e].color = best.0;
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Shape {
+    contours: Vec<Contour>,
+    bounds: Bounds
+}
+
+fn is_corner(a_dir: Vec2, b_dir: Vec2, threshold: Float) -> bool {
+    a_dir.dot(b_dir) <= 0.0 || a_dir.cross(b_dir).abs() > threshold
+}
+
+impl Shape {
+    pub fn new(contours: Vec<Contour>, bounds: Bounds) -> Self {
+        Shape { contours, bounds }
+    }
+
+    /// Creates a shape from the given contours, with bounds computed from their geometry, see [`Shape::compute_bounds`].
+    /// Empty contours are removed.
+    pub fn from_contours(mut contours: Vec<Contour>) -> Self {
+        contours.retain(|c| !c.edges.is_empty());
+
+        let mut shape = Shape { contours, bounds: Bounds::EMPTY };
+        shape.bounds = shape.compute_bounds();
+        if shape.bounds.is_empty() {
+            shape.bounds = Bounds { x_min: 0.0, y_min: 0.0, x_max: 0.0, y_max: 0.0 };
+        }
+        shape
+    }
+
+    /// Returns the exact bounding box of the edges of the shape, including the extrema of curves.
+    /// The bounds are empty if the shape has no edges.
+    pub fn compute_bounds(&self) -> Bounds {
+        let mut bounds = Bounds::EMPTY;
+        for edge in self.contours.iter().flat_map(|c| &c.edges) {
+            bounds.include_bounds(edge.segment.bounds());
+        }
+        bounds
+    }
+
+    /// Returns the bounding box of the shape outlined with a stroke of the given width, centered on its edges.
+    ///
+    /// Corners are joined with miters, which are cut off when they get longer than `miter_limit` times half the stroke width,
+    /// which is conservative for the bevel joins that SVG falls back to past its `stroke-miterlimit`.
+    pub fn compute_outline_bounds(&self, width: Float, miter_limit: Float) -> Bounds {
+        let mut bounds = self.compute_bounds();
+        if bounds.is_empty() || width <= 0.0 {
+            return bounds;
+        }
+
+        let border = width/2.0;
+        bounds.x_min -= border;
+        bounds.y_min -= border;
+        bounds.x_max += border;
+        bounds.y_max += border;
+
+        for contour in &self.contours {
+            let Some(last) = contour.edges.last() else { continue };
+            let mut prev_dir = last.segment.direction(1.0).normalize();
+            for edge in &contour.edges {
+                let dir = edge.segment.direction(0.0).normalize();
+                // the tip of the miter is on the outer side of the turn,
+                // at a distance of the border over the cosine of half the turning angle
+                let outer = prev_dir - dir;
+                if outer.length_sqr() > 0.0 {
+                    let q = 0.5*(1.0 + prev_dir.dot(dir));
+                    let miter_length = if q > 0.0 { (1.0/q.sqrt()).min(miter_limit) } else { miter_limit };
+                    bounds.include(edge.segment.start() + border*miter_length*outer.normalize());
+                }
+                prev_dir = edge.segment.direction(1.0).normalize();
+            }
+        }
+        bounds
+    }
+
+    pub fn contours(&self) -> &[Contour] {
+        &self.contours
+    }
+
+    pub fn bounds(&self) -> Bounds {
+        self.bounds
+    }
+
+    /// Reverses contours so that outer contours are oriented clockwise, and holes counter-clockwise,
+    /// which is what the distance field generators expect for the inside of the shape to be positive.
+    ///
+    /// A contour is considered to be a hole if it is contained by an odd number of other contours.
+    pub fn orient_contours(&mut self) {
+        self.orient_contours_with(FillRule::EvenOdd);
+    }
+
+    /// Same as [`Shape::orient_contours`], with the filled regions decided by the given fill rule
+    /// applied to the contours as they are currently oriented.
+    ///
+    /// A contour is a hole if the region right inside of it isn't filled.
+    /// Contours that are filled on both sides, like overlapping contours with the non-zero rule,
+    /// are oriented clockwise, and merged by [`GeneratorConfig::overlap_support`](super::GeneratorConfig::overlap_support).
+    pub fn orient_contours_with(&mut self, fill_rule: FillRule) {
+        let windings: Vec<_> = self.contours.iter().enumerate().map(|(i, contour)| {
+            let Some(edge) = contour.edges.first() else { return 0 };
+            let p = edge.segment.sample(0.5);
+
+            // winding numbers on both sides of the contour next to p, with the contour itself only counting inside
+            let outside: i32 = self.contours.iter().enumerate()
+                .filter(|&(j, _)| j != i)
+                .map(|(_, other)| Scanline::new(std::slice::from_ref(other), p.y).winding(p.x))
+                .sum();
+            let inside = outside + contour.winding();
+
+            if fill_rule.is_filled(inside) { 1 } else { -1 }
+        }).collect();
+
+        for (contour, winding) in self.contours.iter_mut().zip(windings) {
+            if winding != 0 && contour.winding() != winding {
+                contour.reverse();
+            }
+        }
+    }
+
+    /// Assigns colors to edges of the shape in accordance to the multi-channel distance field
+    /// technique. May split some edges if necessary. `angle` specifies the maximum angle (in
+    /// radians) to be considered a corner, for example 3 (~172 degrees). Values below 1/2 PI will
+    /// be treated as the external angle.
+    /// Necessary for MSDF and MTSDF. The shape is normalized first, see [`Shape::normalize`].
+    pub fn color_edges(mut self, coloring: EdgeColoring, angle: Float, mut seed: u64) -> ColouredShape {
+        self.normalize();
+        let seed = &mut seed;
+        let cross_threshold = angle.sin();
+
+        if coloring == EdgeColoring::Distance {
+            color_by_distance(&mut self.contours, cross_threshold, seed);
+            return ColouredShape { contours: self.contours, bounds: self.bounds };
+        }
+
+        let mut color = init_color(seed);
+
+        // cache corner array across each loop
+        let mut corners = vec![];
+
+        for contour in &mut self.contours {
+            if contour.edges.is_empty() { continue }
+            corners.clear();
+
+            find_corners(contour, cross_threshold, &mut corners);
+
+            // smooth contour
+            if corners.is_empty() {
+                switch_color(&mut color, seed);
+                for edge in &mut contour.edges {
+                    edge.color = color;
+                }
+            } else if corners.len() == 1 { // teardrop shape
+                let mut colors = [Color::BLACK; 3];
+                switch_color(&mut color, seed);
+                colors[0] = color;
+                colors[1] = Color::WHITE;
+                switch_color(&mut color, seed);
+                colors[2] = color;
+
+                let corner = corners[0];
+                let m = contour.edges.len();
+                if m >= 3 { // okay -> color edges appropriately
+                    for i in 0..m {
+                        contour.edges[(corner + i) % m].color = colors[(1 + symmetrical_trichotomy(i as i32, m as i32)) as usize];
+                    }
+                } else if m == 2 { // 1 or 2 edges -> we need to split edges
+                    let a = contour.edges[corner].segment.split_in_three();
+                    let b = contour.edges[1 - corner].segment.split_in_three();
+
+                    // color every 2 edges with the same color
+                    let colors = colors.into_iter().flat_map(|c| [c, c]);
+
+                    contour.edges = a.into_iter().chain(b)
+                        .zip(colors).map(|(s, c)| s.colored(c))
+                        .collect();
+                } else if m == 1 {
+                    contour.edges = contour.edges[0].segment.split_in_three().into_iter()
+                        .zip(colors).map(|(s, c)| s.colored(c))
+                        .collect();
+                }
+            } else if coloring == EdgeColoring::InkTrap {
+                color_ink_trap_corners(contour, &corners, &mut color, seed);
+            } else { // there are multiple corners, but no need to split
+                switch_color(&mut color, seed);
+                let initial_color = color;
+
+                let mut spline = 0;
+                let m = contour.edges.len();
+                let corners_len = corners.len();
+                let start = corners[0];
+                for i in 0..m {
+                    let idx = (start + i) % m;
+                    if spline + 1 < corners_len && corners[spline + 1] == idx {
+                        spline += 1;
+                        switch_color_constrained(&mut color, seed, if spline == corners_len-1 { initial_color } else { Color::BLACK });
+                    }
+                    contour.edges[idx].color = color;
+                }
+            }
+        }
+
+        ColouredShape {
+            contours: self.contours,
+            bounds: self.bounds
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ColouredShape {
+    pub contours: Vec<Contour>,
+    pub bounds: Bounds
+}
@@ -0,0 +1,742 @@
+use super::consts::PI;
+
+use super::{build::{arc_cubics, ShapeBuilder}, scanline::FillRule, shape::Shape, transform::Transform, vec2, Contour, Float, Vec2};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SvgPathError {
+    /// The path data doesn't start with a moveto command
+    MissingMoveTo,
+    /// A character that isn't a command, nor the start of a number, at the given byte position
+    UnexpectedCharacter { position: usize, character: char },
+    /// A number (or arc flag) was expected at the given byte position
+    ExpectedNumber { position: usize }
+}
+
+impl std::fmt::Display for SvgPathError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SvgPathError::MissingMoveTo => write!(f, "path data must start with a moveto command"),
+            SvgPathError::UnexpectedCharacter { position, character } => write!(f, "unexpected character '{character}' at position {position}"),
+            SvgPathError::ExpectedNumber { position } => write!(f, "expected a number at position {position}")
+        }
+    }
+}
+
+impl std::error::Error for SvgPathError {}
+
+struct Lexer<'a> {
+    data: &'a [u8],
+    pos: usize
+}
+
+impl Lexer<'_> {
+    fn skip_separators(&mut self) {
+        while self.pos < self.data.len() && (self.data[self.pos].is_ascii_whitespace() || self.data[self.pos] == b',') {
+            self.pos += 1;
+        }
+    }
+
+    fn peek(&mut self) -> Option<u8> {
+        self.skip_separators();
+        self.data.get(self.pos).copied()
+    }
+
+    fn number(&mut self) -> Result<Float, SvgPathError> {
+        self.skip_separators();
+        let start = self.pos;
+        let digits = |lexer: &mut Self| {
+            let from = lexer.pos;
+            while lexer.pos < lexer.data.len() && lexer.data[lexer.pos].is_ascii_digit() {
+                lexer.pos += 1;
+            }
+            lexer.pos > from
+        };
+
+        if matches!(self.data.get(self.pos), Some(b'+' | b'-')) { self.pos += 1 }
+        let mut has_digits = digits(self);
+        if self.data.get(self.pos) == Some(&b'.') {
+            self.pos += 1;
+            has_digits |= digits(self);
+        }
+        if !has_digits {
+            self.pos = start;
+            return Err(SvgPathError::ExpectedNumber { position: start });
+        }
+
+        // only consume the exponent if it's well formed, so that `e` can't be mistaken for a command
+        if matches!(self.data.get(self.pos), Some(b'e' | b'E')) {
+            let mantissa_end = self.pos;
+            self.pos += 1;
+            if matches!(self.data.get(self.pos), Some(b'+' | b'-')) { self.pos += 1 }
+            if !digits(self) {
+                self.pos = mantissa_end;
+            }
+        }
+
+        // the slice only contains ascii characters
+        let text = std::str::from_utf8(&self.data[start..self.pos]).unwrap();
+        text.parse().map_err(|_| SvgPathError::ExpectedNumber { position: start })
+    }
+
+    fn point(&mut self) -> Result<Vec2, SvgPathError> {
+        Ok(vec2(self.number()?, self.number()?))
+    }
+
+    /// Arc flags are a single `0` or `1`, which don't need to be separated from what follows.
+    fn flag(&mut self) -> Result<bool, SvgPathError> {
+        match self.peek() {
+            Some(b'0') => { self.pos += 1; Ok(false) }
+            Some(b'1') => { self.pos += 1; Ok(true) }
+            _ => Err(SvgPathError::ExpectedNumber { position: self.pos })
+        }
+    }
+}
+
+/// Feeds SVG path commands to a [`ShapeBuilder`], keeping track of the current subpath.
+///
+/// Points are given in SVG coordinates. They are transformed and flipped vertically before being passed to the builder,
+/// since the Y axis of SVG points down while the one of shapes points up.
+#[derive(Default)]
+struct PathSink {
+    builder: ShapeBuilder,
+    transform: Transform,
+    current: Vec2,
+    subpath_start: Vec2,
+    subpath_open: bool
+}
+
+impl PathSink {
+    fn with_transform(transform: Transform) -> Self {
+        PathSink { transform, ..Default::default() }
+    }
+
+    fn out(&self, p: Vec2) -> Vec2 {
+        let p = self.transform.apply(p);
+        vec2(p.x, -p.y)
+    }
+
+    fn move_to(&mut self, p: Vec2) {
+        self.close();
+        self.current = p;
+        self.subpath_start = p;
+    }
+
+    /// Starts a new subpath at the current point if the previous one was closed.
+    fn begin(&mut self) {
+        if !self.subpath_open {
+            self.builder.move_to(self.out(self.current));
+            self.subpath_open = true;
+        }
+    }
+
+    fn line_to(&mut self, p: Vec2) {
+        self.begin();
+        self.builder.line_to(self.out(p));
+        self.current = p;
+    }
+
+    fn quad_to(&mut self, c: Vec2, p: Vec2) {
+        self.begin();
+        self.builder.quad_to(self.out(c), self.out(p));
+        self.current = p;
+    }
+
+    fn cubic_to(&mut self, c1: Vec2, c2: Vec2, p: Vec2) {
+        self.begin();
+        self.builder.cubic_to(self.out(c1), self.out(c2), self.out(p));
+        self.current = p;
+    }
+
+    /// Draws an elliptical arc from the current point to `p`, as described by the SVG specification.
+    fn arc_to(&mut self, radii: Vec2, x_axis_rotation: Float, large_arc: bool, sweep: bool, p: Vec2) {
+        let p0 = self.current;
+        if p0 == p { return }
+
+        let (mut rx, mut ry) = (radii.x.abs(), radii.y.abs());
+        if rx == 0.0 || ry == 0.0 {
+            self.line_to(p);
+            return;
+        }
+
+        // conversion from endpoint to center parameterization
+        let (sin, cos) = x_axis_rotation.to_radians().sin_cos();
+        let half = (p0 - p)*0.5;
+        let x1 = cos*half.x + sin*half.y;
+        let y1 = -sin*half.x + cos*half.y;
+
+        let lambda = (x1*x1)/(rx*rx) + (y1*y1)/(ry*ry);
+        if lambda > 1.0 {
+            rx *= lambda.sqrt();
+            ry *= lambda.sqrt();
+        }
+
+        let num = rx*rx*ry*ry - rx*rx*y1*y1 - ry*ry*x1*x1;
+        let den = rx*rx*y1*y1 + ry*ry*x1*x1;
+        let mut coef = (num/den).max(0.0).sqrt();
+        if large_arc == sweep { coef = -coef }
+        let cx1 = coef*rx*y1/ry;
+        let cy1 = -coef*ry*x1/rx;
+
+        let mid = (p0 + p)*0.5;
+        let center = vec2(cos*cx1 - sin*cy1 + mid.x, sin*cx1 + cos*cy1 + mid.y);
+
+        let angle = |u: Vec2, v: Vec2| u.cross(v).atan2(u.dot(v));
+        let start_vec = vec2((x1 - cx1)/rx, (y1 - cy1)/ry);
+        let end_vec = vec2((-x1 - cx1)/rx, (-y1 - cy1)/ry);
+        let start_angle = angle(vec2(1.0, 0.0), start_vec);
+        let mut sweep_angle = angle(start_vec, end_vec);
+        if !sweep && sweep_angle > 0.0 { sweep_angle -= 2.0*PI }
+        if sweep && sweep_angle < 0.0 { sweep_angle += 2.0*PI }
+
+        let map = |v: Vec2| vec2(center.x + cos*v.x*rx - sin*v.y*ry, center.y + sin*v.x*rx + cos*v.y*ry);
+        let mut cubics = arc_cubics(start_angle, sweep_angle).peekable();
+        while let Some((c1, c2, end)) = cubics.next() {
+            // snap to the exact end point to avoid accumulating error
+            let end = if cubics.peek().is_none() { p } else { map(end) };
+            self.cubic_to(map(c1), map(c2), end);
+        }
+    }
+
+    fn close(&mut self) {
+        if self.subpath_open {
+            self.builder.close();
+            self.subpath_open = false;
+        }
+        self.current = self.subpath_start;
+    }
+}
+
+/// Parses SVG path data (the `d` attribute of a `<path>` element) into the given builder.
+/// Points are flipped vertically, see [`Shape::from_svg_path`].
+fn parse_path(d: &str, sink: &mut PathSink) -> Result<(), SvgPathError> {
+    let mut lexer = Lexer { data: d.as_bytes(), pos: 0 };
+    let mut command: Option<u8> = None;
+    // reflected control point for the smooth curve commands, with whether it comes from a cubic curve
+    let mut last_control: Option<(Vec2, bool)> = None;
+
+    while let Some(c) = lexer.peek() {
+        let cmd = if c.is_ascii_alphabetic() {
+            if command.is_none() && !matches!(c, b'M' | b'm') {
+                return Err(SvgPathError::MissingMoveTo);
+            }
+            lexer.pos += 1;
+            c
+        } else {
+            match command {
+                None => return Err(SvgPathError::MissingMoveTo),
+                // coordinates following a moveto are implicit lineto commands
+                Some(b'M') => b'L',
+                Some(b'm') => b'l',
+                Some(b'Z' | b'z') => {
+                    let character = d[lexer.pos..].chars().next().unwrap();
+                    return Err(SvgPathError::UnexpectedCharacter { position: lexer.pos, character });
+                }
+                Some(command) => command
+            }
+        };
+        command = Some(cmd);
+
+        let origin = if cmd.is_ascii_lowercase() { sink.current } else { Vec2::default() };
+        let mut control = None;
+        match cmd.to_ascii_uppercase() {
+            b'M' => sink.move_to(origin + lexer.point()?),
+            b'L' => sink.line_to(origin + lexer.point()?),
+            b'H' => {
+                let x = lexer.number()? + origin.x;
+                sink.line_to(vec2(x, sink.current.y));
+            }
+            b'V' => {
+                let y = lexer.number()? + origin.y;
+                sink.line_to(vec2(sink.current.x, y));
+            }
+            b'Q' => {
+                let c = origin + lexer.point()?;
+                sink.quad_to(c, origin + lexer.point()?);
+                control = Some((c, false));
+            }
+            b'T' => {
+                let c = match last_control {
+                    Some((c, false)) => sink.current*2.0 - c,
+                    _ => sink.current
+                };
+                sink.quad_to(c, origin + lexer.point()?);
+                control = Some((c, false));
+            }
+            b'C' => {
+                let c1 = origin + lexer.point()?;
+                let c2 = origin + lexer.point()?;
+                sink.cubic_to(c1, c2, origin + lexer.point()?);
+                control = Some((c2, true));
+            }
+            b'S' => {
+                let c1 = match last_control {
+                    Some((c, true)) => sink.current*2.0 - c,
+                    _ => sink.current
+                };
+                let c2 = origin + lexer.point()?;
+                sink.cubic_to(c1, c2, origin + lexer.point()?);
+                control = Some((c2, true));
+            }
+            b'A' => {
+                let radii = lexer.point()?;
+                let rotation = lexer.number()?;
+                let large_arc = lexer.flag()?;
+                let sweep = lexer.flag()?;
+                sink.arc_to(radii, rotation, large_arc, sweep, origin + lexer.point()?);
+            }
+            b'Z' => sink.close(),
+            _ => return Err(SvgPathError::UnexpectedCharacter { position: lexer.pos - 1, character: cmd as char })
+        }
+        last_control = control;
+    }
+
+    Ok(())
+}
+
+impl Shape {
+    /// Creates a shape from SVG path data, the `d` attribute of a `<path>` element.
+    /// Every command of the specification is supported, and arcs are converted to cubic curves.
+    ///
+    /// The Y axis is flipped, so that the shape is the right way up when generated like a glyph.
+    /// Unclosed subpaths are closed, and contours are reoriented following the `nonzero` fill rule,
+    /// the default of SVG, see [`Shape::orient_contours_with`].
+    pub fn from_svg_path(d: &str) -> Result<Shape, SvgPathError> {
+        let mut sink = PathSink::default();
+        parse_path(d, &mut sink)?;
+        Ok(sink.into_shape(FillRule::NonZero))
+    }
+
+    /// Creates a shape from an SVG document, merging every `<path>`, `<rect>`, `<circle>`, `<ellipse>`,
+    /// `<polygon>` and `<polyline>` element into a single shape.
+    ///
+    /// Nested `transform` attributes, and the position and `viewBox` of nested `<svg>` elements are applied,
+    /// but their viewports don't clip their content. Elements that aren't rendered, or have no fill, are skipped.
+    /// Like with [`Shape::from_svg_path`], the Y axis is flipped, and the contours of each element are reoriented,
+    /// following its `fill-rule` (see [`Shape::orient_contours_with`]), so overlapping elements are merged together.
+    /// The bounds of the shape are those of its geometry.
+    pub fn from_svg(text: &str) -> Result<Shape, SvgError> {
+        let document = roxmltree::Document::parse(text)?;
+        let root = document.root_element();
+        if root.tag_name().name() != "svg" {
+            return Err(SvgError::NotSvg);
+        }
+
+        let mut contours = vec![];
+        visit_element(root, Transform::default(), Fill::default(), &mut contours)?;
+        Ok(Shape::from_contours(contours))
+    }
+
+    /// Loads an SVG file, see [`Shape::from_svg`].
+    pub fn load_svg(path: impl AsRef<std::path::Path>) -> Result<Shape, SvgError> {
+        let text = std::fs::read_to_string(path)?;
+        Shape::from_svg(&text)
+    }
+}
+
+#[derive(Debug)]
+pub enum SvgError {
+    Io(std::io::Error),
+    Xml(roxmltree::Error),
+    Path(SvgPathError),
+    /// The root element of the document isn't `<svg>`
+    NotSvg
+}
+
+impl std::fmt::Display for SvgError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SvgError::Io(e) => write!(f, "failed to read svg file: {e}"),
+            SvgError::Xml(e) => write!(f, "failed to parse svg document: {e}"),
+            SvgError::Path(e) => write!(f, "invalid path data: {e}"),
+            SvgError::NotSvg => write!(f, "root element of the document is not <svg>")
+        }
+    }
+}
+
+impl std::error::Error for SvgError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SvgError::Io(e) => Some(e),
+            SvgError::Xml(e) => Some(e),
+            SvgError::Path(e) => Some(e),
+            SvgError::NotSvg => None
+        }
+    }
+}
+
+impl From<std::io::Error> for SvgError {
+    fn from(e: std::io::Error) -> Self { SvgError::Io(e) }
+}
+
+impl From<roxmltree::Error> for SvgError {
+    fn from(e: roxmltree::Error) -> Self { SvgError::Xml(e) }
+}
+
+impl From<SvgPathError> for SvgError {
+    fn from(e: SvgPathError) -> Self { SvgError::Path(e) }
+}
+
+impl PathSink {
+    fn into_shape(self, fill_rule: FillRule) -> Shape {
+        let mut shape = self.builder.build();
+        shape.orient_contours_with(fill_rule);
+        shape
+    }
+}
+
+/// Returns the value of a presentation attribute, which can also be set in the `style` attribute.
+fn presentation_attribute<'a>(node: roxmltree::Node<'a, '_>, name: &str) -> Option<&'a str> {
+    let from_style = node.attribute("style").and_then(|style| {
+        style.split(';')
+            .filter_map(|declaration| declaration.split_once(':'))
+            .find(|(key, _)| key.trim() == name)
+            .map(|(_, value)| value.trim())
+    });
+
+    from_style.or_else(|| node.attribute(name).map(str::trim))
+}
+
+/// Parses a length in user units, with an optional `px` suffix. Other units are not supported.
+fn parse_length(value: &str) -> Option<Float> {
+    value.trim().trim_end_matches("px").trim_end().parse().ok()
+}
+
+fn length_attribute(node: roxmltree::Node, name: &str) -> Float {
+    node.attribute(name).and_then(parse_length).unwrap_or(0.0)
+}
+
+/// Parses a list of numbers separated by whitespace and/or commas.
+fn parse_numbers(value: &str) -> Option<Vec<Float>> {
+    let mut lexer = Lexer { data: value.as_bytes(), pos: 0 };
+    let mut numbers = vec![];
+    while lexer.peek().is_some() {
+        numbers.push(lexer.number().ok()?);
+    }
+    Some(numbers)
+}
+
+/// Parses the value of a `transform` attribute. Returns `None` if it is malformed.
+fn parse_transform(value: &str) -> Option<Transform> {
+    let mut transform = Transform::default();
+    let mut rest = value.trim_start_matches(|c: char| c.is_whitespace() || c == ',');
+    while !rest.is_empty() {
+        let (name, after) = rest.split_once('(')?;
+        let (args, after) = after.split_once(')')?;
+        let args = parse_numbers(args)?;
+
+        let t = match (name.trim(), args.as_slice()) {
+            ("matrix", &[a, b, c, d, e, f]) => Transform { a, b, c, d, e, f },
+            ("translate", &[x]) => Transform::translate(x, 0.0),
+            ("translate", &[x, y]) => Transform::translate(x, y),
+            ("scale", &[s]) => Transform::scale(s, s),
+            ("scale", &[x, y]) => Transform::scale(x, y),
+            ("rotate", &[angle, ..]) => {
+                let rotation = Transform::rotate(angle.to_radians());
+                match args[1..] {
+                    [] => rotation,
+                    [cx, cy] => Transform::translate(cx, cy).then(rotation).then(Transform::translate(-cx, -cy)),
+                    _ => return None
+                }
+            }
+            ("skewX", &[angle]) => Transform::skew_x(angle.to_radians()),
+            ("skewY", &[angle]) => Transform::skew_y(angle.to_radians()),
+            _ => return None
+        };
+
+        transform = transform.then(t);
+        rest = after.trim_start_matches(|c: char| c.is_whitespace() || c == ',');
+    }
+    Some(transform)
+}
+
+/// Returns the transform mapping the `viewBox` of an `<svg>` element to its viewport,
+/// following the default `preserveAspectRatio` behavior (centered, and scaled uniformly to fit).
+fn viewbox_transform(svg: roxmltree::Node) -> Transform {
+    let Some(&[min_x, min_y, width, height]) = svg.attribute("viewBox").and_then(parse_numbers).as_deref() else {
+        return Transform::default();
+    };
+    if width <= 0.0 || height <= 0.0 {
+        return Transform::default();
+    }
+
+    let viewport_width = svg.attribute("width").and_then(parse_length).unwrap_or(width);
+    let viewport_height = svg.attribute("height").and_then(parse_length).unwrap_or(height);
+
+    let scale = (viewport_width/width).min(viewport_height/height);
+    let offset_x = (viewport_width - width*scale)/2.0;
+    let offset_y = (viewport_height - height*scale)/2.0;
+    Transform::translate(offset_x, offset_y)
+        .then(Transform::scale(scale, scale))
+        .then(Transform::translate(-min_x, -min_y))
+}
+
+/// The inherited properties deciding how an element is filled.
+#[derive(Debug, Clone, Copy)]
+struct Fill {
+    filled: bool,
+    rule: FillRule
+}
+
+impl Default for Fill {
+    fn default() -> Self {
+        Fill { filled: true, rule: FillRule::NonZero }
+    }
+}
+
+impl Fill {
+    /// Returns the fill of an element, with the properties it doesn't set inherited from its parent.
+    fn of(node: roxmltree::Node, parent: Fill) -> Self {
+        let filled = match presentation_attribute(node, "fill") {
+            None | Some("inherit") => parent.filled,
+            Some(fill) => fill != "none"
+        };
+        let rule = match presentation_attribute(node, "fill-rule") {
+            Some("nonzero") => FillRule::NonZero,
+            Some("evenodd") => FillRule::EvenOdd,
+            _ => parent.rule
+        };
+        Fill { filled, rule }
+    }
+}
+
+/// Adds the contours of the element and its children, if it's rendered and filled.
+fn visit_element(node: roxmltree::Node, parent_transform: Transform, parent_fill: Fill, contours: &mut Vec<Contour>) -> Result<(), SvgError> {
+    if presentation_attribute(node, "display") == Some("none") {
+        return Ok(());
+    }
+
+    let fill = Fill::of(node, parent_fill);
+    let mut transform = match node.attribute("transform").and_then(parse_transform) {
+        Some(t) => parent_transform.then(t),
+        None => parent_transform
+    };
+
+    let name = node.tag_name().name();
+    if let "svg" | "g" | "a" = name {
+        if name == "svg" {
+            // the position of the root element is ignored
+            if node.parent_element().is_some() {
+                transform = transform.then(Transform::translate(length_attribute(node, "x"), length_attribute(node, "y")));
+            }
+            transform = transform.then(viewbox_transform(node));
+        }
+
+        for child in node.children().filter(|n| n.is_element()) {
+            visit_element(child, transform, fill, contours)?;
+        }
+        return Ok(());
+    }
+    if !fill.filled {
+        return Ok(());
+    }
+
+    let mut sink = PathSink::with_transform(transform);
+    match name {
+        "path" => parse_path(node.attribute("d").unwrap_or(""), &mut sink)?,
+        "rect" => {
+            let (x, y) = (length_attribute(node, "x"), length_attribute(node, "y"));
+            let (w, h) = (length_attribute(node, "width"), length_attribute(node, "height"));
+            if w <= 0.0 || h <= 0.0 { return Ok(()) }
+
+            let (rx, ry) = match (node.attribute("rx").and_then(parse_length), node.attribute("ry").and_then(parse_length)) {
+                (Some(rx), Some(ry)) => (rx, ry),
+                (Some(r), None) | (None, Some(r)) => (r, r),
+                (None, None) => (0.0, 0.0)
+            };
+            let radii = vec2(rx.clamp(0.0, w/2.0), ry.clamp(0.0, h/2.0));
+            let (rx, ry) = (radii.x, radii.y);
+
+            sink.move_to(vec2(x + rx, y));
+            sink.line_to(vec2(x + w - rx, y));
+            sink.arc_to(radii, 0.0, false, true, vec2(x + w, y + ry));
+            sink.line_to(vec2(x + w, y + h - ry));
+            sink.arc_to(radii, 0.0, false, true, vec2(x + w - rx, y + h));
+            sink.line_to(vec2(x + rx, y + h));
+            sink.arc_to(radii, 0.0, false, true, vec2(x, y + h - ry));
+            sink.line_to(vec2(x, y + ry));
+            sink.arc_to(radii, 0.0, false, true, vec2(x + rx, y));
+        }
+        name @ ("circle" | "ellipse") => {
+            let center = vec2(length_attribute(node, "cx"), length_attribute(node, "cy"));
+            let radii = if name == "circle" {
+                let r = length_attribute(node, "r");
+                vec2(r, r)
+            } else {
+                vec2(length_attribute(node, "rx"), length_attribute(node, "ry"))
+            };
+            if radii.x <= 0.0 || radii.y <= 0.0 { return Ok(()) }
+
+            sink.move_to(center + vec2(radii.x, 0.0));
+            sink.arc_to(radii, 0.0, false, true, center + vec2(0.0, radii.y));
+            sink.arc_to(radii, 0.0, false, true, center - vec2(radii.x, 0.0));
+            sink.arc_to(radii, 0.0, false, true, center - vec2(0.0, radii.y));
+            sink.arc_to(radii, 0.0, false, true, center + vec2(radii.x, 0.0));
+        }
+        "polygon" | "polyline" => {
+            let points = node.attribute("points").and_then(parse_numbers).unwrap_or_default();
+            let mut points = points.chunks_exact(2).map(|p| vec2(p[0], p[1]));
+            let Some(first) = points.next() else { return Ok(()) };
+
+            sink.move_to(first);
+            for p in points {
+                sink.line_to(p);
+            }
+        }
+        _ => return Ok(())
+    }
+
+    contours.extend(sink.into_shape(fill.rule).contours().iter().cloned());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sdf::Scanline;
+
+    type Contours = Vec<Vec<Vec<(Float, Float)>>>;
+
+    /// Parses path data, returning the points of every segment of every contour, in SVG coordinates.
+    fn parse(d: &str) -> Result<Contours, SvgPathError> {
+        let mut sink = PathSink::default();
+        parse_path(d, &mut sink)?;
+        Ok(sink.builder.into_contours().iter()
+            .map(|c| c.edges.iter().map(|e| e.segment.points().iter().map(|p| (p.x, -p.y)).collect()).collect())
+            .collect())
+    }
+
+    fn square() -> Contours {
+        vec![vec![
+            vec![(10.0, 20.0), (30.0, 20.0)],
+            vec![(30.0, 20.0), (30.0, 40.0)],
+            vec![(30.0, 40.0), (10.0, 40.0)],
+            vec![(10.0, 40.0), (10.0, 20.0)]
+        ]]
+    }
+
+    #[test]
+    fn lines() {
+        assert_eq!(parse("M10 20 L30 20 L30 40 L10 40 Z"), Ok(square()));
+        assert_eq!(parse("m10 20 l20 0 l0 20 l-20 0 z"), Ok(square()));
+        assert_eq!(parse("M10 20 H30 V40 H10 Z"), Ok(square()));
+        assert_eq!(parse("m10 20 h20 v20 h-20 z"), Ok(square()));
+    }
+
+    #[test]
+    fn implicit_commands() {
+        // coordinates after a moveto are lines, and other commands repeat
+        assert_eq!(parse("M10 20 30 20 30 40 10 40Z"), Ok(square()));
+        assert_eq!(parse("m10,20,20,0,0,20,-20,0z"), Ok(square()));
+        assert_eq!(parse("M10 20 L30 20 30 40 10 40 Z"), Ok(square()));
+        assert_eq!(parse("M10 20 H30 V40 H20 10 Z"), Ok(vec![vec![
+            vec![(10.0, 20.0), (30.0, 20.0)],
+            vec![(30.0, 20.0), (30.0, 40.0)],
+            vec![(30.0, 40.0), (20.0, 40.0)],
+            vec![(20.0, 40.0), (10.0, 40.0)],
+            vec![(10.0, 40.0), (10.0, 20.0)]
+        ]]));
+    }
+
+    #[test]
+    fn numbers() {
+        assert_eq!(parse("M1e1 2E1L3e+1 20 30 400e-1 1.e1 40Z"), Ok(square()));
+        assert_eq!(parse("M10-20.5.5-0Z"), Ok(vec![vec![vec![(10.0, -20.5), (0.5, 0.0)], vec![(0.5, 0.0), (10.0, -20.5)]]]));
+    }
+
+    #[test]
+    fn subpaths() {
+        // after closing, the next subpath starts at the start of the previous one
+        let contours = parse("M0 0 H10 V10 Z l5 5 h5 z M20 20 h5 v5").unwrap();
+        assert_eq!(contours.len(), 3);
+        assert_eq!(contours[1][0], vec![(0.0, 0.0), (5.0, 5.0)]);
+        assert_eq!(contours[2][0], vec![(20.0, 20.0), (25.0, 20.0)]);
+        // open subpaths are closed
+        assert_eq!(contours[2].last(), Some(&vec![(25.0, 25.0), (20.0, 20.0)]));
+    }
+
+    #[test]
+    fn quadratic_curves() {
+        let expected = vec![vec![
+            vec![(0.0, 0.0), (10.0, 10.0), (20.0, 0.0)],
+            vec![(20.0, 0.0), (30.0, -10.0), (40.0, 0.0)],
+            vec![(40.0, 0.0), (50.0, 10.0), (60.0, 0.0)],
+            vec![(60.0, 0.0), (0.0, 0.0)]
+        ]];
+        assert_eq!(parse("M0 0 Q10 10 20 0 T40 0 T60 0 Z"), Ok(expected.clone()));
+        assert_eq!(parse("m0 0 q10 10 20 0 t20 0 t20 0 z"), Ok(expected));
+
+        // without a previous quadratic curve, the control point is the current point
+        assert_eq!(parse("M0 0 C0 10 10 10 10 0 T20 0 Z").unwrap()[0][1], vec![(10.0, 0.0), (10.0, 0.0), (20.0, 0.0)]);
+    }
+
+    #[test]
+    fn cubic_curves() {
+        let expected = vec![vec![
+            vec![(0.0, 0.0), (0.0, 10.0), (10.0, 10.0), (10.0, 0.0)],
+            vec![(10.0, 0.0), (10.0, -10.0), (20.0, -10.0), (20.0, 0.0)],
+            vec![(20.0, 0.0), (30.0, 10.0), (40.0, 10.0), (40.0, 0.0)],
+            vec![(40.0, 0.0), (0.0, 0.0)]
+        ]];
+        assert_eq!(parse("M0 0 C0 10 10 10 10 0 S20 -10 20 0 C30 10 40 10 40 0 Z"), Ok(expected.clone()));
+        assert_eq!(parse("m0 0 c0 10 10 10 10 0 s10 -10 10 0 c10 10 20 10 20 0 z"), Ok(expected));
+
+        // without a previous cubic curve, the first control point is the current point
+        assert_eq!(parse("M0 0 Q10 10 20 0 S30 10 40 0 Z").unwrap()[0][1], vec![(20.0, 0.0), (20.0, 0.0), (30.0, 10.0), (40.0, 0.0)]);
+    }
+
+    #[test]
+    fn arcs() {
+        for d in ["M0 0 A10 10 0 0 1 20 0", "M0 0 a10 10 0 0 1 20 0", "M0 0a10 10 0 0120 0", "M0,0A10,10,0,0,1,20,0"] {
+            let contour = &parse(d).unwrap()[0];
+            // a half circle, going through the top in SVG coordinates, and the closing line
+            assert_eq!(contour.len(), 3, "{d}");
+            assert_eq!(contour[0][0], (0.0, 0.0), "{d}");
+            assert_eq!(contour[1][3], (20.0, 0.0), "{d}");
+            assert!((contour[0][3].0 - 10.0).abs() < 1e-4 && (contour[0][3].1 + 10.0).abs() < 1e-4, "{d}: {contour:?}");
+        }
+
+        // the sweep flag picks the center, and the large arc flag the long way around it
+        let middle = |c: &Vec<(Float, Float)>| ((c[0].0 + 3.0*(c[1].0 + c[2].0) + c[3].0)/8.0, (c[0].1 + 3.0*(c[1].1 + c[2].1) + c[3].1)/8.0);
+        let on_circle = |c: &Vec<(Float, Float)>, center: (Float, Float)| {
+            [c[3], middle(c)].iter().all(|p| ((p.0 - center.0).hypot(p.1 - center.1) - 10.0).abs() < 0.01)
+        };
+        let contour = &parse("M0 0 a10 10 0 0 1 10 10").unwrap()[0];
+        assert!(contour.len() == 2 && on_circle(&contour[0], (0.0, 10.0)), "{contour:?}");
+        let contour = &parse("M0 0 a10 10 0 0 0 10 10").unwrap()[0];
+        assert!(contour.len() == 2 && on_circle(&contour[0], (10.0, 0.0)), "{contour:?}");
+        let contour = &parse("M0 0 a10 10 0 1 0 10 10").unwrap()[0];
+        assert!(contour.len() == 4 && contour[..3].iter().all(|c| on_circle(c, (0.0, 10.0))), "{contour:?}");
+
+        // radii that are too small are scaled up, and zero radii draw a line
+        assert_eq!(parse("M0 0 A1 1 0 0 1 20 0").unwrap()[0].len(), 3);
+        assert_eq!(parse("M0 0 A0 10 0 0 1 20 0 Z"), Ok(vec![vec![vec![(0.0, 0.0), (20.0, 0.0)], vec![(20.0, 0.0), (0.0, 0.0)]]]));
+    }
+
+    #[test]
+    fn overlapping_subpaths() {
+        // two squares drawn in the same direction, overlapping in the middle
+        let d = "M0 0 H10 V10 H0 Z M5 5 H15 V15 H5 Z";
+        let shape = Shape::from_svg_path(d).unwrap();
+        assert_eq!(shape.contours().iter().map(Contour::winding).collect::<Vec<_>>(), [1, 1]);
+        assert_eq!(Scanline::new(shape.contours(), -7.0).winding(7.0), 2);
+
+        let document = Shape::from_svg(&format!(r#"<svg xmlns="http://www.w3.org/2000/svg"><path d="{d}"/></svg>"#)).unwrap();
+        assert_eq!(format!("{:?}", document.contours()), format!("{:?}", shape.contours()));
+
+        // with the even-odd rule, the overlap is a hole
+        let document = Shape::from_svg(&format!(r#"<svg xmlns="http://www.w3.org/2000/svg"><path d="{d}" fill-rule="evenodd"/></svg>"#)).unwrap();
+        assert_eq!(Scanline::new(document.contours(), -7.0).winding(7.0), 0);
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(parse("L10 10"), Err(SvgPathError::MissingMoveTo));
+        assert_eq!(parse("10 10"), Err(SvgPathError::MissingMoveTo));
+        assert_eq!(parse("M0 0 L10"), Err(SvgPathError::ExpectedNumber { position: 8 }));
+        assert_eq!(parse("M0 0 L10 ."), Err(SvgPathError::ExpectedNumber { position: 9 }));
+        assert_eq!(parse("M0 0 X10 10"), Err(SvgPathError::UnexpectedCharacter { position: 5, character: 'X' }));
+        assert_eq!(parse("M0 0 L10 10 Z 5 5"), Err(SvgPathError::UnexpectedCharacter { position: 14, character: '5' }));
+        assert_eq!(parse("M0 0 A10 10 0 2 1 20 0"), Err(SvgPathError::ExpectedNumber { position: 14 }));
+        assert_eq!(parse("M0 0 L10 #"), Err(SvgPathError::ExpectedNumber { position: 9 }));
+    }
+}
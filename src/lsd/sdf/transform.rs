@@ -0,0 +1,94 @@
+use super::{shape::Shape, vec2, Float, Segment, Vec2};
+
+/// A 2D affine transform, as a 2×3 matrix: `x' = a*x + c*y + e`, `y' = b*x + d*y + f`.
+/// This is the same layout as the `matrix` of SVG transforms.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transform {
+    pub a: Float, pub b: Float, pub c: Float,
+    pub d: Float, pub e: Float, pub f: Float
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Transform { a: 1.0, b: 0.0, c: 0.0, d: 1.0, e: 0.0, f: 0.0 }
+    }
+}
+
+impl Transform {
+    pub fn translate(x: Float, y: Float) -> Self {
+        Transform { e: x, f: y, ..Default::default() }
+    }
+
+    pub fn scale(x: Float, y: Float) -> Self {
+        Transform { a: x, d: y, ..Default::default() }
+    }
+
+    /// Rotates counter-clockwise by the given angle in radians, in a Y up space.
+    pub fn rotate(angle: Float) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        Transform { a: cos, b: sin, c: -sin, d: cos, e: 0.0, f: 0.0 }
+    }
+
+    /// Slants vertical lines by the given angle in radians, leaning right for positive angles in a Y up space,
+    /// like synthetic italics.
+    pub fn skew_x(angle: Float) -> Self {
+        Transform { c: angle.tan(), ..Default::default() }
+    }
+
+    /// Slants horizontal lines by the given angle in radians.
+    pub fn skew_y(angle: Float) -> Self {
+        Transform { b: angle.tan(), ..Default::default() }
+    }
+
+    /// Returns the transform applying `other`, and then `self`.
+    pub fn then(self, other: Transform) -> Transform {
+        Transform {
+            a: self.a*other.a + self.c*other.b,
+            b: self.b*other.a + self.d*other.b,
+            c: self.a*other.c + self.c*other.d,
+            d: self.b*other.c + self.d*other.d,
+            e: self.a*other.e + self.c*other.f + self.e,
+            f: self.b*other.e + self.d*other.f + self.f
+        }
+    }
+
+    pub fn apply(&self, p: Vec2) -> Vec2 {
+        vec2(self.a*p.x + self.c*p.y + self.e, self.b*p.x + self.d*p.y + self.f)
+    }
+
+    /// Returns the determinant of the linear part of the transform,
+    /// which is negative for transforms that mirror shapes.
+    pub fn determinant(&self) -> Float {
+        self.a*self.d - self.b*self.c
+    }
+}
+
+impl Segment {
+    /// Returns the segment with every point transformed, which is the transformed curve.
+    pub fn transform(self, t: &Transform) -> Self {
+        match self {
+            Segment::Line(a, b) => Segment::Line(t.apply(a), t.apply(b)),
+            Segment::Quad(a, b, c) => Segment::Quad(t.apply(a), t.apply(b), t.apply(c)),
+            Segment::Cubic(a, b, c, d) => Segment::Cubic(t.apply(a), t.apply(b), t.apply(c), t.apply(d))
+        }
+    }
+}
+
+impl Shape {
+    /// Applies the transform to every edge of the shape, and recomputes its bounds
+    /// like [`Shape::from_contours`].
+    ///
+    /// Contours are reversed when the transform mirrors the shape, so that filled contours stay clockwise.
+    pub fn transform(&mut self, t: &Transform) {
+        let mut contours = self.contours().to_vec();
+        for contour in &mut contours {
+            for edge in &mut contour.edges {
+                edge.segment = edge.segment.transform(t);
+            }
+            if t.determinant() < 0.0 {
+                contour.reverse();
+            }
+        }
+        *self = Shape::from_contours(contours);
+    }
+}
@@ -0,0 +1,241 @@
+use super::{shape::Shape, Bounds, Contour, Face, GlyphId, Segment, ShapeBuilder, Vec2};
+
+/// A problem with the outline of a shape.
+/// Edges are identified by their index in their contour, and contours by their index in the shape.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShapeError {
+    /// The glyph has no outline, or it couldn't be read
+    MissingOutline,
+    /// An edge was drawn before starting a contour with `move_to`
+    MissingMoveTo,
+    /// `close` was called while no contour was being drawn
+    RedundantClose,
+    /// A contour was started before the previous one was closed.
+    /// Contours without edges are dropped by [`ShapeBuilder`], and aren't counted.
+    UnclosedContour { contour: usize },
+    /// A point of the edge is NaN or infinite
+    NonFiniteCoordinate { contour: usize, edge: usize },
+    /// Every point of the edge is the same
+    ZeroLengthSegment { contour: usize, edge: usize },
+    /// The edge doesn't end where the next edge of the contour starts
+    DisconnectedEdges { contour: usize, edge: usize },
+    /// The last edge of the contour doesn't end where the first one starts
+    OpenContour { contour: usize }
+}
+
+impl std::fmt::Display for ShapeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ShapeError::MissingOutline => write!(f, "the glyph has no outline"),
+            ShapeError::MissingMoveTo => write!(f, "an edge was drawn before the first move_to"),
+            ShapeError::RedundantClose => write!(f, "close was called while no contour was open"),
+            ShapeError::UnclosedContour { contour } => write!(f, "contour {contour} was never closed"),
+            ShapeError::NonFiniteCoordinate { contour, edge } => write!(f, "edge {edge} of contour {contour} has a non-finite coordinate"),
+            ShapeError::ZeroLengthSegment { contour, edge } => write!(f, "edge {edge} of contour {contour} has a length of zero"),
+            ShapeError::DisconnectedEdges { contour, edge } => write!(f, "edge {edge} of contour {contour} doesn't end where the next edge starts"),
+            ShapeError::OpenContour { contour } => write!(f, "contour {contour} doesn't end where it starts")
+        }
+    }
+}
+
+impl std::error::Error for ShapeError {}
+
+/// What to do with a malformed outline.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ValidationMode {
+    /// Fails with the first problem found.
+    Strict,
+    /// Fixes every problem that can be fixed, see [`Shape::repair`].
+    #[default]
+    Repair
+}
+
+fn is_finite(segment: &Segment) -> bool {
+    segment.points().iter().all(|p| p.x.is_finite() && p.y.is_finite())
+}
+
+fn is_zero_length(segment: &Segment) -> bool {
+    let start = segment.start();
+    segment.points().iter().all(|&p| p == start)
+}
+
+/// Returns whether `p` is on the line segment going from `a` to `b`.
+fn is_on_line(p: Vec2, a: Vec2, b: Vec2) -> bool {
+    if a == b { return p == a }
+
+    let (ab, ap) = (b - a, p - a);
+    ab.cross(ap) == 0.0 && (0.0..=ab.length_sqr()).contains(&ab.dot(ap))
+}
+
+/// Returns the lowest order segment with the same geometry.
+fn simplify(segment: Segment) -> Segment {
+    match segment {
+        Segment::Quad(a, b, c) if is_on_line(b, a, c) => Segment::Line(a, c),
+        Segment::Cubic(a, b, c, d) if is_on_line(b, a, d) && is_on_line(c, a, d) => Segment::Line(a, d),
+        // a quadratic curve elevated to a cubic one
+        Segment::Cubic(a, b, c, d) if 3.0*b - a == 3.0*c - d => Segment::Quad(a, 0.5*(3.0*b - a), d),
+        segment => segment
+    }
+}
+
+/// Returns whether an edge ending at `end` meets a following edge starting at `next`, up to rounding errors.
+pub fn meets(end: Vec2, next: Vec2) -> bool {
+    (end - next).length() <= 1e-5*next.length().max(1.0)
+}
+
+/// Connects `segment` to a following edge starting at `next`.
+/// Moves the end of the segment if it only misses `next` because of rounding errors,
+/// otherwise returns the line needed to fill the gap.
+pub fn connect(segment: &mut Segment, next: Vec2) -> Option<Segment> {
+    let end = segment.end();
+    if end == next { return None }
+
+    if meets(end, next) {
+        *segment = segment.with_end(next);
+        None
+    } else {
+        Some(Segment::Line(end, next))
+    }
+}
+
+impl Contour {
+    fn repair(&mut self) {
+        self.edges.retain(|e| is_finite(&e.segment) && !is_zero_length(&e.segment));
+
+        let count = self.edges.len();
+        let mut edges = Vec::with_capacity(count);
+        for i in 0..count {
+            let mut edge = self.edges[i];
+            let next = self.edges[(i + 1) % count].segment.start();
+            let gap = connect(&mut edge.segment, next);
+
+            edges.push(edge);
+            edges.extend(gap.map(|s| s.colored(edge.color)));
+        }
+        self.edges = edges;
+    }
+}
+
+impl Shape {
+    /// Outlines the given glyph, as a normalized shape (see [`Shape::normalize`]).
+    ///
+    /// With [`ValidationMode::Strict`], any problem with the outline is returned as an error,
+    /// otherwise the outline is repaired, and only glyphs without an outline fail.
+    pub fn from_glyph(face: &Face, glyph: GlyphId, mode: ValidationMode) -> Result<Self, ShapeError> {
+        let mut builder = ShapeBuilder::new();
+        face.outline_glyph(glyph, &mut builder).ok_or(ShapeError::MissingOutline)?;
+
+        builder.end();
+        if mode == ValidationMode::Strict {
+            if let Some(&error) = builder.errors().first() {
+                return Err(error);
+            }
+        }
+
+        let mut shape = Shape::new(builder.into_contours(), Bounds::EMPTY);
+        match mode {
+            ValidationMode::Strict => shape.validate()?,
+            ValidationMode::Repair => shape.repair()
+        }
+        shape.normalize();
+        // the bounding box of the font only bounds the control points, it's computed from the curves instead
+        Ok(Shape::from_contours(shape.contours().to_vec()))
+    }
+
+    /// Checks the geometry of the shape, returning the first problem found.
+    ///
+    /// Edges that miss the next one only because of rounding errors are considered connected.
+    pub fn validate(&self) -> Result<(), ShapeError> {
+        for (c, contour) in self.contours().iter().enumerate() {
+            let count = contour.edges.len();
+            for (e, edge) in contour.edges.iter().enumerate() {
+                if !is_finite(&edge.segment) {
+                    return Err(ShapeError::NonFiniteCoordinate { contour: c, edge: e });
+                }
+                if is_zero_length(&edge.segment) {
+                    return Err(ShapeError::ZeroLengthSegment { contour: c, edge: e });
+                }
+                if !meets(edge.segment.end(), contour.edges[(e + 1) % count].segment.start()) {
+                    return Err(if e + 1 == count {
+                        ShapeError::OpenContour { contour: c }
+                    } else {
+                        ShapeError::DisconnectedEdges { contour: c, edge: e }
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Removes edges that have a non-finite coordinate or a length of zero, connects edges that don't meet
+    /// and closes open contours, so that [`Shape::validate`] succeeds. Contours left empty are removed.
+    pub fn repair(&mut self) {
+        let mut contours = self.contours().to_vec();
+        for contour in &mut contours {
+            contour.repair();
+        }
+        contours.retain(|c| !c.edges.is_empty());
+        *self = Shape::new(contours, self.bounds());
+    }
+
+    /// Prepares the shape for edge coloring and distance computation, without changing its geometry:
+    /// curves with aligned control points are turned into lower order segments,
+    /// edges with a length of zero are removed,
+    /// and contours made of a single edge are split in three, so that they can be colored.
+    pub fn normalize(&mut self) {
+        let mut contours = self.contours().to_vec();
+        for contour in &mut contours {
+            for edge in &mut contour.edges {
+                edge.segment = simplify(edge.segment);
+            }
+            contour.edges.retain(|e| !is_zero_length(&e.segment));
+
+            if let [edge] = contour.edges[..] {
+                contour.edges = edge.segment.split_in_three().map(|s| s.colored(edge.color)).to_vec();
+            }
+        }
+        contours.retain(|c| !c.edges.is_empty());
+        *self = Shape::new(contours, self.bounds());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sdf::{vec2, OutlineBuilder};
+
+    #[test]
+    fn zero_length_segments() {
+        let mut builder = ShapeBuilder::new();
+        builder.move_to(vec2(0.0, 0.0)).line_to(vec2(0.0, 10.0)).line_to(vec2(0.0, 10.0));
+        OutlineBuilder::curve_to(&mut builder, 0.0, 10.0, 0.0, 10.0, 0.0, 10.0);
+        builder.line_to(vec2(10.0, 10.0)).close();
+
+        let mut shape = Shape::new(builder.into_contours(), Bounds::EMPTY);
+        assert_eq!(shape.validate(), Err(ShapeError::ZeroLengthSegment { contour: 0, edge: 1 }));
+
+        shape.repair();
+        assert_eq!(shape.validate(), Ok(()));
+        assert_eq!(shape.contours()[0].edges.len(), 3);
+
+        let mut builder = ShapeBuilder::new();
+        builder.move_to(vec2(0.0, 0.0)).line_to(vec2(0.0, 10.0));
+        OutlineBuilder::curve_to(&mut builder, 0.0, 10.0, 0.0, 10.0, 0.0, 10.0);
+        builder.line_to(vec2(10.0, 10.0)).close();
+        assert_eq!(builder.try_build().err(), Some(ShapeError::ZeroLengthSegment { contour: 0, edge: 1 }));
+    }
+
+    #[test]
+    fn empty_contours_are_not_counted() {
+        let mut builder = ShapeBuilder::new();
+        builder.move_to(vec2(0.0, 0.0)).close();
+        builder.move_to(vec2(5.0, 5.0));
+        builder.move_to(vec2(0.0, 0.0)).line_to(vec2(0.0, 10.0)).line_to(vec2(10.0, 10.0));
+        builder.move_to(vec2(20.0, 0.0)).line_to(vec2(20.0, 10.0)).line_to(vec2(30.0, 10.0)).close();
+        assert_eq!(builder.errors(), &[ShapeError::UnclosedContour { contour: 0 }]);
+
+        let contours = builder.into_contours();
+        assert_eq!(contours.len(), 2);
+        assert_eq!(contours[0].edges[0].segment.start(), vec2(0.0, 0.0));
+    }
+}
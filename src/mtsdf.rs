@@ -5,8 +5,6 @@ use image::buffer::ConvertBuffer;
 use lsd::*;
 use sdl3_sys::{events::{SDL_EVENT_QUIT, SDL_EVENT_WINDOW_RESIZED}, gpu::{SDL_GPUVertexAttribute, SDL_GPUVertexBufferDescription, SDL_GPUVertexElementFormat, SDL_GPUVertexInputRate}};
 
-pub mod sdf;
-
 use nalgebra::{Matrix4, Vector2, Vector3, Vector4};
 
 type Vec2 = Vector2<f32>;
@@ -32,9 +30,9 @@ fn main() {
     let font = include_bytes!("/usr/share/fonts/TTF/Iosevka-Medium.ttc").as_slice();
     let font = ttf_parser::Face::parse(font, 0).unwrap();
 
-    // let mtsdf = sdf::generate_mtsdf(&font);
+    // let mtsdf = sdf::generate_mtsdf(&font, 4096);
 
-    // let mtsdf: image::RgbaImage = mtsdf.convert();
+    // let mtsdf: image::RgbaImage = mtsdf.pages[0].convert();
     // let rendered: image::RgbaImage = rendered.convert();
 
     // mtsdf.save("out.png").unwrap();